@@ -0,0 +1,66 @@
+//! Compares two ways of preparing the DS4 submit request on the report hot path:
+//! building the whole struct per call, and overwriting the fields of a per-thread
+//! struct whose `size` was set once. The driver backend builds the struct per call,
+//! since the per-thread struct did not come out ahead.
+//!
+//! The IOCTL itself is left out, so this only measures the copy into the request.
+//! Run with `cargo bench --bench ds4_submit`.
+
+use std::cell::RefCell;
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use vigem_rust::Ds4Report;
+
+// Same layout as the request the crate sends with `IOCTL_DS4_SUBMIT_REPORT`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct Ds4SubmitReport {
+    size: u32,
+    serial_no: u32,
+    report: Ds4Report,
+}
+
+thread_local! {
+    static SUBMIT_REPORT: RefCell<Ds4SubmitReport> = RefCell::new(Ds4SubmitReport {
+        size: size_of::<Ds4SubmitReport>() as u32,
+        ..Default::default()
+    });
+}
+
+fn submit(request: &Ds4SubmitReport) {
+    black_box(request);
+}
+
+fn ds4_submit(c: &mut Criterion) {
+    let report = Ds4Report {
+        thumb_lx: 200,
+        trigger_r: 255,
+        ..Default::default()
+    };
+
+    let mut group = c.benchmark_group("ds4_submit");
+    group.bench_function("per_call", |b| {
+        b.iter(|| {
+            let request = Ds4SubmitReport {
+                size: size_of::<Ds4SubmitReport>() as u32,
+                serial_no: black_box(1),
+                report: *black_box(&report),
+            };
+            submit(&request);
+        })
+    });
+    group.bench_function("thread_local", |b| {
+        b.iter(|| {
+            SUBMIT_REPORT.with_borrow_mut(|request| {
+                request.serial_no = black_box(1);
+                request.report = *black_box(&report);
+                submit(request);
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ds4_submit);
+criterion_main!(benches);
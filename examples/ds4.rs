@@ -1,6 +1,5 @@
 use std::thread;
-use std::time::Duration;
-use vigem_rust::{Client, Ds4Button, Ds4Dpad, Ds4Report};
+use vigem_rust::{Client, Ds4Button, Ds4Dpad};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Connect to the ViGEm bus
@@ -34,20 +33,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
-    // Here, we'll send reports to the controller to simulate input.
-
-    let mut report = Ds4Report::default();
-
-    // Hold the right D-pad button
-    report.set_dpad(Ds4Dpad::East);
-    // Hold the Cross button
-    report.buttons |= Ds4Button::CROSS.bits();
-    // Fully press the right trigger
-    report.trigger_r = 255;
-
+    // Here, we'll send reports to the controller to simulate input, 60 times a second.
     let mut angle: f64 = 0.0;
 
-    loop {
+    ds4.run_at_hz(60, |report| {
+        // Hold the right D-pad button
+        report.set_dpad(Ds4Dpad::East);
+        // Hold the Cross button
+        report.buttons |= Ds4Button::CROSS.bits();
+        // Fully press the right trigger
+        report.trigger_r = 255;
+
         // Animate the right thumbstick in a circle
         let (sin, cos) = angle.sin_cos();
 
@@ -55,10 +51,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         report.thumb_rx = (128.0 + sin * 127.0) as u8;
         report.thumb_ry = (128.0 + cos * 127.0) as u8;
 
-        // Send the updated report to the controller
-        ds4.update(&report)?;
-
-        thread::sleep(Duration::from_millis(16));
         angle += 0.05;
-    }
+        // Keep running until the process is stopped
+        true
+    })?;
+
+    Ok(())
 }
@@ -1,7 +1,8 @@
 use std::thread;
 use std::time::Duration;
 use vigem_rust::Client;
-use vigem_rust::controller::ds4::Ds4ReportEx;
+use vigem_rust::controller::ds4::{Ds4Touch, Ds4TouchSequencer};
+use vigem_rust::target::Ds4ExUpdater;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Connect to the ViGEm bus
@@ -33,11 +34,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
-    // Main input loop for extended reports
-    let mut report_ex = Ds4ReportEx::default();
+    // The sequencer keeps track of the touch packet counter and history for us,
+    // and the updater advances the report timestamp like a real controller.
+    let mut sequencer = Ds4TouchSequencer::new();
+    let mut updater = Ds4ExUpdater::new(ds4);
 
     // Variables to animate the touch point
-    let mut packet_counter: u8 = 0;
     let mut touch_x: i32 = 0;
     let mut direction: i32 = 12;
 
@@ -51,20 +53,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         touch_x += direction;
 
-        report_ex.touch_packets_n = 1;
-
-        // Get a mut reference to the touch data struct inside the report.
-        let touch = &mut report_ex.current_touch;
-
-        // This counter should increment for each new packet of touch data (not sure if necessary for functionality).
-        touch.packet_counter = packet_counter;
-        packet_counter = packet_counter.wrapping_add(1);
-
+        let mut touch = Ds4Touch::default();
         touch.set_touch_1(true, 1, touch_x as u16, 471); // Finger 1 is down, centered vertically.
         touch.set_touch_2(false, 0, 0, 0); // Finger 2 is up (inactive).
 
+        let report_ex = sequencer.push_frame(touch);
+
         // Send the updated report to the controller
-        ds4.update_ex(&report_ex)?;
+        updater.update_ex(&report_ex)?;
 
         thread::sleep(Duration::from_millis(16));
     }
@@ -0,0 +1,80 @@
+// Compares the per-update cost of the report submit path with and without a fresh
+// event per call.
+//
+// `update` reuses a per-thread overlapped event. The "per-call event" run adds a
+// `CreateEventW`/`CloseHandle` pair to every update, approximating the submit path
+// from before the event was cached. Both runs print their time per update and the process
+// handle count before and after, so the difference can be read off directly.
+#[cfg(windows)]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::{Duration, Instant};
+    use vigem_rust::{Client, X360Button, X360Report};
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        CreateEventW, GetCurrentProcess, GetProcessHandleCount,
+    };
+
+    const ITERATIONS: u32 = 10_000;
+
+    fn handle_count() -> windows::core::Result<u32> {
+        let mut count = 0;
+        unsafe { GetProcessHandleCount(GetCurrentProcess(), &mut count)? };
+        Ok(count)
+    }
+
+    let client = Client::connect()?;
+    let x360 = client.new_x360_target().plugin()?;
+    x360.wait_for_ready()?;
+    println!("Controller is ready, submitting {ITERATIONS} reports per run...");
+
+    let run = |label: &str, per_call_event: bool| -> Result<(), Box<dyn std::error::Error>> {
+        let mut report = X360Report::default();
+        let handles_before = handle_count()?;
+        let start = Instant::now();
+
+        for i in 0..ITERATIONS {
+            // Toggle a button so every report actually differs.
+            report.buttons = if i % 2 == 0 {
+                X360Button::A
+            } else {
+                X360Button::empty()
+            };
+
+            if per_call_event {
+                let event = unsafe { CreateEventW(None, false, false, None)? };
+                x360.update(&report)?;
+                unsafe { CloseHandle(event)? };
+            } else {
+                x360.update(&report)?;
+            }
+        }
+
+        let elapsed = start.elapsed();
+        let handles_after = handle_count()?;
+        println!("{label}:");
+        println!("  Per update: {:?}", elapsed / ITERATIONS);
+        println!(
+            "  Updates per second: {:.0}",
+            ITERATIONS as f64 / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+        );
+        println!(
+            "  Events created: {}",
+            if per_call_event { ITERATIONS } else { 0 }
+        );
+        println!("  Process handles: {handles_before} before, {handles_after} after");
+        Ok(())
+    };
+
+    // Warm up, so the cached event of this thread already exists in both runs.
+    x360.update(&X360Report::default())?;
+
+    run("Cached event", false)?;
+    run("Per-call event", true)?;
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("This benchmark needs the ViGEmBus driver, which only runs on Windows.");
+}
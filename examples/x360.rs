@@ -1,6 +1,5 @@
 use std::thread;
-use std::time::Duration;
-use vigem_rust::{Client, X360Button, X360Report};
+use vigem_rust::{Client, X360Button};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Connect to the ViGEm bus
@@ -33,12 +32,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     });
 
-    // Here, we'll send reports to the controller to simulate input.
-    let mut report = X360Report::default();
+    // Here, we'll send reports to the controller to simulate input, 60 times a second.
     let mut angle: f64 = 0.0;
     let mut step = 0;
 
-    loop {
+    x360.run_at_hz(60, |report| {
         // Animate the left thumbstick in a circle
         angle += 0.1;
         let (sin, cos) = angle.sin_cos();
@@ -52,10 +50,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             report.buttons = X360Button::B;
         }
 
-        // Send the updated report to the controller
-        x360.update(&report)?;
-
-        thread::sleep(Duration::from_millis(16));
         step += 1;
-    }
+        // Keep running until the process is stopped
+        true
+    })?;
+
+    Ok(())
 }
@@ -1,40 +1,153 @@
+use bitflags::bitflags;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
 use thiserror::Error;
 
+#[cfg(feature = "x360")]
+use crate::controller::x360::X360Report;
 use crate::internal::bus::{Bus, BusError};
+#[cfg(windows)]
+use crate::internal::driver::is_transient_windows_error;
+use crate::internal::logging::{debug, trace, warn};
+#[cfg(feature = "mock")]
+use crate::mock::MockBus;
 #[cfg(feature = "ds4")]
 use crate::target::DualShock4;
 #[cfg(feature = "x360")]
-use crate::target::Xbox360;
+use crate::target::{TargetType, Xbox360};
 
-use crate::target::{Target, TargetBuilder, TargetHandle};
+use crate::target::{ControllerKind, Target, TargetBuilder, TargetHandle, TargetKind};
 
 /// Errors that can occur when interacting with the ViGEm client.
 #[derive(Error, Debug)]
 pub enum ClientError {
+    #[cfg(windows)]
     #[error("Windows API Error: {0}")]
     WindowsAPIError(#[from] windows::core::Error),
 
     #[error("Bus error: {0}")]
-    BusError(#[from] BusError),
+    BusError(BusError),
+
+    #[error("The installed ViGEmBus driver version is not supported, consider updating it")]
+    DriverVersionMismatch,
 
     #[error("No more free slots available, consider increasing slots via the Client builder")]
     NoFreeSlot,
 
+    #[error("Failed to plug in target with serial ID {0}: {1}")]
+    PluginFailed(u32, #[source] BusError),
+
     #[error("Target with serial ID {0} is no longer connected or has been unplugged")]
     TargetDoesNotExist(u32),
 
     #[error("Client has been dropped, therefore any target operations can't be done.")]
     ClientNoLongerExists,
+
+    #[error("Connecting to the ViGEm bus did not complete within {0:?}")]
+    ConnectTimeout(Duration),
+
+    #[error("Invalid VID {0:#06x} / PID {1:#06x}, neither may be zero")]
+    InvalidIdentifiers(u16, u16),
+
+    #[error("No matching notification arrived within {0:?}")]
+    NotificationTimeout(Duration),
+
+    #[error("Client mutex was poisoned by a thread that panicked while holding it")]
+    Poisoned,
+
+    #[error("Can't lower max_targets to {0} while {1} targets are plugged in")]
+    MaxTargetsTooLow(u32, usize),
+
+    #[error("Serial {0} is not a valid slot, serials run from 1 to max_targets ({1})")]
+    InvalidSerial(u32, u32),
+}
+
+impl ClientError {
+    /// Returns `true` if the error is likely temporary and retrying the same
+    /// operation may succeed.
+    ///
+    /// This covers Windows API errors such as `ERROR_BUSY` or `ERROR_NOT_READY`, as
+    /// well as [`ClientError::ConnectTimeout`] and [`ClientError::NotificationTimeout`].
+    /// Errors that can't resolve themselves, like [`ClientError::ClientNoLongerExists`]
+    /// or [`ClientError::TargetDoesNotExist`], return `false`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::{thread, time::Duration};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::connect()?;
+    ///
+    /// let x360 = loop {
+    ///     match client.new_x360_target().plugin() {
+    ///         Err(e) if e.is_transient() => thread::sleep(Duration::from_millis(100)),
+    ///         other => break other?,
+    ///     }
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(windows)]
+            ClientError::WindowsAPIError(e) => is_transient_windows_error(e),
+            ClientError::BusError(e) | ClientError::PluginFailed(_, e) => e.is_transient(),
+            ClientError::ConnectTimeout(_) | ClientError::NotificationTimeout(_) => true,
+            ClientError::DriverVersionMismatch
+            | ClientError::NoFreeSlot
+            | ClientError::InvalidIdentifiers(..)
+            | ClientError::TargetDoesNotExist(_)
+            | ClientError::ClientNoLongerExists
+            | ClientError::Poisoned
+            | ClientError::MaxTargetsTooLow(..)
+            | ClientError::InvalidSerial(..) => false,
+        }
+    }
+}
+
+impl From<BusError> for ClientError {
+    fn from(error: BusError) -> Self {
+        match error {
+            BusError::VersionMismatch => ClientError::DriverVersionMismatch,
+            BusError::TargetNotFound(serial_no) => ClientError::TargetDoesNotExist(serial_no),
+            other => ClientError::BusError(other),
+        }
+    }
+}
+
+/// A virtual controller attached to the ViGEm bus, as reported by
+/// [`Client::enumerate_system_targets`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemTargetInfo {
+    /// The serial number (bus slot) of the target.
+    pub serial_no: u32,
+    /// The controller type, or `None` if it couldn't be determined.
+    pub kind: Option<ControllerKind>,
+    /// The USB Vendor ID the target was plugged in with.
+    pub vendor_id: u16,
+    /// The USB Product ID the target was plugged in with.
+    pub product_id: u16,
+    /// Whether the target was plugged in by this `Client`.
+    pub owned: bool,
 }
 
 const DEFAULT_VIGEM_TARGETS_MAX: u32 = 16;
 
+type UnplugErrorHandler = Box<dyn Fn(u32, BusError) + Send>;
+
 pub(crate) struct ClientInner {
     pub(crate) bus: Bus,
     pub(crate) targets: HashMap<u32, Target>,
     max_targets: u32,
+    serial_strategy: SerialStrategy,
+    // Where the next scan starts with `SerialStrategy::Monotonic`.
+    next_serial: u32,
+    unplug_error_handler: Option<UnplugErrorHandler>,
 }
 
 /// The main entry point for interacting with the ViGEm bus driver.
@@ -48,16 +161,115 @@ pub struct Client {
     inner: Arc<Mutex<ClientInner>>,
 }
 
+/// The scheduling priority of the background threads that deliver notifications.
+///
+/// See [`ClientBuilder::thread_priority`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThreadPriority {
+    /// The default priority of newly created threads.
+    #[default]
+    Normal,
+    /// One step above normal priority.
+    AboveNormal,
+    /// The highest priority for non-realtime processes. Use with care, as a busy
+    /// thread at this priority can starve the rest of the system.
+    TimeCritical,
+}
+
+/// How a [`Client`] picks the serial number (bus slot) for a new target.
+///
+/// See [`ClientBuilder::serial_strategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerialStrategy {
+    /// Always use the lowest free serial, so an unplugged serial is reused right away.
+    #[default]
+    LowestFree,
+    /// Continue after the most recently assigned serial, wrapping around within
+    /// `max_targets`. This delays the reuse of serials, which some games and drivers
+    /// handle poorly when it happens in quick succession.
+    Monotonic,
+}
+
+bitflags! {
+    /// File flags used when opening the bus device, see [`ClientBuilder::io_flags`].
+    ///
+    /// `FILE_FLAG_OVERLAPPED` is always set on top of these, as every driver request is
+    /// issued as overlapped I/O.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct IoFlags: u32 {
+        /// `FILE_FLAG_NO_BUFFERING`
+        const NO_BUFFERING  = 1 << 0;
+        /// `FILE_FLAG_WRITE_THROUGH`
+        const WRITE_THROUGH = 1 << 1;
+    }
+}
+
+impl Default for IoFlags {
+    /// Both flags are set by default.
+    fn default() -> Self {
+        IoFlags::NO_BUFFERING | IoFlags::WRITE_THROUGH
+    }
+}
+
+/// A snapshot of the activity counters of a [`Client`], see [`ClientBuilder::with_metrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Metrics {
+    /// Input reports successfully submitted to any target of the client.
+    pub reports_submitted: u64,
+    /// Notifications (rumble, LED, output buffers) received from the driver.
+    pub notifications_received: u64,
+    /// Targets successfully plugged into the bus.
+    pub plug_count: u64,
+    /// Targets successfully unplugged from the bus.
+    pub unplug_count: u64,
+}
+
+/// The live counters behind [`Metrics`], shared with the bus and its notification threads.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsCounters {
+    pub(crate) reports_submitted: AtomicU64,
+    pub(crate) notifications_received: AtomicU64,
+    pub(crate) plug_count: AtomicU64,
+    pub(crate) unplug_count: AtomicU64,
+}
+
+impl MetricsCounters {
+    fn snapshot(&self) -> Metrics {
+        Metrics {
+            reports_submitted: self.reports_submitted.load(Ordering::Relaxed),
+            notifications_received: self.notifications_received.load(Ordering::Relaxed),
+            plug_count: self.plug_count.load(Ordering::Relaxed),
+            unplug_count: self.unplug_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// A builder for creating a `Client`.
 pub struct ClientBuilder {
     max_targets: Option<u32>,
+    connect_timeout: Option<Duration>,
+    thread_priority: Option<ThreadPriority>,
+    serial_strategy: Option<SerialStrategy>,
+    io_flags: Option<IoFlags>,
+    bus_index: Option<usize>,
+    reclaim_on_connect: bool,
+    metrics: bool,
 }
 
 impl ClientBuilder {
     #[inline]
     /// Creates a new `ClientBuilder` with default settings.
     fn new() -> Self {
-        Self { max_targets: None }
+        Self {
+            max_targets: None,
+            connect_timeout: None,
+            thread_priority: None,
+            serial_strategy: None,
+            io_flags: None,
+            bus_index: None,
+            reclaim_on_connect: false,
+            metrics: false,
+        }
     }
 
     #[inline]
@@ -69,14 +281,170 @@ impl ClientBuilder {
         self
     }
 
+    #[inline]
+    /// Bounds how long connecting to the bus may take.
+    ///
+    /// Connecting enumerates and opens the bus device, which can block indefinitely if
+    /// the driver is in a bad state. With a timeout set, the connection is attempted on
+    /// a helper thread and [`ClientError::ConnectTimeout`] is returned if it doesn't
+    /// complete in time.
+    ///
+    /// By default, no timeout is applied.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    #[inline]
+    /// Sets the priority of the background threads that receive notifications.
+    ///
+    /// Rumble and LED notifications are delivered by dedicated threads, which can lag
+    /// behind on a loaded system. A higher priority lets them react sooner. Report
+    /// submission is not affected, as it runs on the caller's thread.
+    ///
+    /// The default is [`ThreadPriority::Normal`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::{Client, ThreadPriority};
+    /// let client = Client::builder()
+    ///     .thread_priority(ThreadPriority::AboveNormal)
+    ///     .connect()
+    ///     .unwrap();
+    /// ```
+    pub fn thread_priority(mut self, priority: ThreadPriority) -> Self {
+        self.thread_priority = Some(priority);
+        self
+    }
+
+    #[inline]
+    /// Sets how serial numbers are assigned to newly plugged targets.
+    ///
+    /// Targets plugged in with an explicit serial through
+    /// [`TargetBuilder::with_serial`] are not affected.
+    ///
+    /// The default is [`SerialStrategy::LowestFree`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::{Client, SerialStrategy};
+    /// let client = Client::builder()
+    ///     .serial_strategy(SerialStrategy::Monotonic)
+    ///     .connect()
+    ///     .unwrap();
+    /// ```
+    pub fn serial_strategy(mut self, strategy: SerialStrategy) -> Self {
+        self.serial_strategy = Some(strategy);
+        self
+    }
+
+    #[inline]
+    /// Overrides the file flags used when opening the bus device.
+    ///
+    /// This is an escape hatch for nonstandard driver builds, some of which fail report
+    /// submissions on a handle opened with `FILE_FLAG_NO_BUFFERING`. The default is
+    /// [`IoFlags::default()`], which has both flags set.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::{Client, IoFlags};
+    /// let client = Client::builder()
+    ///     .io_flags(IoFlags::WRITE_THROUGH)
+    ///     .connect()
+    ///     .unwrap();
+    /// ```
+    pub fn io_flags(mut self, flags: IoFlags) -> Self {
+        self.io_flags = Some(flags);
+        self
+    }
+
+    #[inline]
+    /// Connects to the ViGEm bus at `index` in device enumeration order.
+    ///
+    /// By default, the first bus that accepts this crate's interface version is used.
+    /// On machines with more than one ViGEm bus, e.g. with nested or virtualized driver
+    /// stacks, this selects a specific one instead. Only that bus is tried, and
+    /// connecting fails with a "bus not found" [`ClientError::BusError`] if there is no
+    /// bus at `index`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::Client;
+    /// let client = Client::builder().bus_index(1).connect().unwrap();
+    /// ```
+    pub fn bus_index(mut self, index: usize) -> Self {
+        self.bus_index = Some(index);
+        self
+    }
+
+    #[inline]
+    /// Unplugs every serial up to `max_targets` while connecting.
+    ///
+    /// After a crash, the targets of the previous process can stay plugged in as "ghost"
+    /// controllers and occupy their serials. With this enabled, `connect` tries to unplug
+    /// serials `1..=max_targets` before the client plugs in anything, ignoring serials
+    /// that are free.
+    ///
+    /// **WARNING:** The bus doesn't tell which process owns a target, so this also
+    /// unplugs the controllers of other running clients. It is off by default.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::Client;
+    /// let client = Client::builder().reclaim_on_connect(true).connect().unwrap();
+    /// ```
+    pub fn reclaim_on_connect(mut self, reclaim: bool) -> Self {
+        self.reclaim_on_connect = reclaim;
+        self
+    }
+
+    #[inline]
+    /// Enables counting of the client's activity, readable through [`Client::metrics`].
+    ///
+    /// The counters are plain atomics, so the overhead is negligible, but they are off by
+    /// default.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::client::Client;
+    /// let client = Client::builder().with_metrics().connect().unwrap();
+    ///
+    /// // ... run the load test ...
+    ///
+    /// println!("{:?}", client.metrics());
+    /// ```
+    pub fn with_metrics(mut self) -> Self {
+        self.metrics = true;
+        self
+    }
+
     /// Connects to the ViGEm bus and creates a `Client`.
     pub fn connect(self) -> Result<Client, ClientError> {
         let max_targets = self.max_targets.unwrap_or(DEFAULT_VIGEM_TARGETS_MAX);
-        let bus = Bus::connect()?;
+        let io_flags = self.io_flags.unwrap_or_default();
+        let mut bus = match self.connect_timeout {
+            Some(timeout) => connect_with_timeout(timeout, io_flags, self.bus_index)?,
+            None => Bus::connect(io_flags, self.bus_index)?,
+        }
+        .with_notification_priority(self.thread_priority.unwrap_or_default());
+        if self.reclaim_on_connect {
+            debug!("Reclaiming serials 1..={max_targets}");
+            for serial_no in 1..=max_targets {
+                // Most serials are expected to be free, which the bus reports as an error.
+                let _ = bus.unplug(serial_no);
+            }
+        }
+        // Enabled after reclaiming, so that only this client's activity is counted.
+        if self.metrics {
+            bus = bus.with_metrics(Arc::default());
+        }
         let inner = ClientInner {
             bus,
             targets: HashMap::new(),
             max_targets,
+            serial_strategy: self.serial_strategy.unwrap_or_default(),
+            next_serial: 1,
+            unplug_error_handler: None,
         };
 
         Ok(Client {
@@ -85,6 +453,31 @@ impl ClientBuilder {
     }
 }
 
+/// Runs `Bus::connect` on a helper thread, giving up after `timeout`.
+///
+/// If the timeout elapses, the helper thread is left to finish on its own and
+/// the bus it may eventually open is closed when its result is discarded.
+fn connect_with_timeout(
+    timeout: Duration,
+    io_flags: IoFlags,
+    bus_index: Option<usize>,
+) -> Result<Bus, ClientError> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("vigem-connect".to_string())
+        .spawn(move || {
+            let _ = sender.send(Bus::connect(io_flags, bus_index));
+        })
+        .map_err(BusError::from)?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Ok(result?),
+        Err(RecvTimeoutError::Timeout) => Err(ClientError::ConnectTimeout(timeout)),
+        Err(RecvTimeoutError::Disconnected) => Err(BusError::BusNotFound.into()),
+    }
+}
+
 impl Client {
     #[inline]
     /// Create a builder to configure and connect a new client.
@@ -103,6 +496,28 @@ impl Client {
         ClientBuilder::new()
     }
 
+    #[inline]
+    /// Returns whether support for the given controller type was compiled in.
+    ///
+    /// Each controller type is behind a cargo feature (`x360` and `ds4`). This lets code
+    /// that picks the controller type at runtime report a missing feature gracefully.
+    /// No connection to the bus is needed.
+    ///
+    /// # Example
+    /// ```
+    /// use vigem_rust::{Client, ControllerKind};
+    ///
+    /// if !Client::supports(ControllerKind::DualShock4) {
+    ///     eprintln!("DS4 support not built in");
+    /// }
+    /// ```
+    pub const fn supports(kind: ControllerKind) -> bool {
+        match kind {
+            ControllerKind::Xbox360 => cfg!(feature = "x360"),
+            ControllerKind::DualShock4 => cfg!(feature = "ds4"),
+        }
+    }
+
     #[inline]
     /// Creates a new client with default options and connects to the ViGEm bus.
     ///
@@ -118,6 +533,40 @@ impl Client {
         Self::builder().connect()
     }
 
+    #[cfg(feature = "mock")]
+    /// Creates a client with default options on an in-memory bus instead of the driver.
+    ///
+    /// The returned [`MockBus`] records every request the client makes and can send
+    /// notifications to its targets. See the [`mock`](crate::mock) module for details.
+    ///
+    /// # Example
+    /// ```
+    /// use vigem_rust::Client;
+    ///
+    /// let (client, bus) = Client::mock();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    /// assert_eq!(bus.plugged(), [1]);
+    ///
+    /// x360.unplug().unwrap();
+    /// assert!(bus.plugged().is_empty());
+    /// ```
+    pub fn mock() -> (Self, MockBus) {
+        let mock = MockBus::default();
+        let inner = ClientInner {
+            bus: Bus::mock(mock.clone()),
+            targets: HashMap::new(),
+            max_targets: DEFAULT_VIGEM_TARGETS_MAX,
+            serial_strategy: SerialStrategy::default(),
+            next_serial: 1,
+            unplug_error_handler: None,
+        };
+
+        let client = Client {
+            inner: Arc::new(Mutex::new(inner)),
+        };
+        (client, mock)
+    }
+
     #[inline]
     #[cfg(feature = "x360")]
     /// Creates a builder for a new virtual Xbox 360 controller.
@@ -139,6 +588,61 @@ impl Client {
         TargetBuilder::new(self)
     }
 
+    #[cfg(feature = "x360")]
+    /// Plugs in `count` virtual Xbox 360 controllers with the default VID and PID.
+    ///
+    /// The client is locked once for the whole batch instead of once per target. The
+    /// batch is all-or-nothing: if any target fails to plug in, the ones already
+    /// plugged in by this call are unplugged again and the error is returned.
+    ///
+    /// As with targets plugged in through a [`TargetBuilder`], call `wait_for_ready` on
+    /// each handle before sending updates.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// let pool = client.plugin_many_x360(8).unwrap();
+    /// assert_eq!(pool.len(), 8);
+    /// ```
+    pub fn plugin_many_x360(
+        &self,
+        count: usize,
+    ) -> Result<Vec<TargetHandle<Xbox360>>, ClientError> {
+        let (vendor_id, product_id) = TargetType::Xbox360.get_identifiers();
+        let mut inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+
+        let mut serials = Vec::with_capacity(count);
+        for _ in 0..count {
+            let target = Target {
+                kind: TargetType::Xbox360,
+                serial_no: 0,
+                vendor_id,
+                product_id,
+                skip_driver_wait: false,
+            };
+
+            match inner.plug(target, None) {
+                Ok(serial_no) => serials.push(serial_no),
+                Err(e) => {
+                    for serial_no in serials {
+                        if inner.targets.remove(&serial_no).is_some() {
+                            inner.unplug_reporting(serial_no);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(serials
+            .into_iter()
+            .map(|serial_no| {
+                TargetHandle::new(serial_no, inner.bus.clone(), Arc::downgrade(&self.inner))
+            })
+            .collect())
+    }
+
     #[inline]
     #[cfg(feature = "ds4")]
     /// Creates a builder for a new virtual DualShock 4 controller.
@@ -160,35 +664,382 @@ impl Client {
         TargetBuilder::new(self)
     }
 
-    pub(crate) fn plugin_internal<T>(
+    /// Returns the maximum number of targets this client can manage.
+    ///
+    /// This is the value set through [`ClientBuilder::max_targets`] or
+    /// [`set_max_targets`](Client::set_max_targets).
+    pub fn max_targets(&self) -> u32 {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_targets
+    }
+
+    /// Changes the maximum number of targets this client can manage.
+    ///
+    /// This lets an application grow its controller budget without reconnecting. The
+    /// limit only affects plugging in new targets, so lowering it never unplugs anything.
+    ///
+    /// # Errors
+    /// Returns [`ClientError::MaxTargetsTooLow`] if `count` is lower than the number of
+    /// targets currently plugged in.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// client.set_max_targets(client.max_targets() + 4).unwrap();
+    /// ```
+    pub fn set_max_targets(&self, count: u32) -> Result<(), ClientError> {
+        let mut inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+        let plugged = inner.targets.len();
+        if (count as usize) < plugged {
+            return Err(ClientError::MaxTargetsTooLow(count, plugged));
+        }
+
+        inner.max_targets = count;
+        // The monotonic scan must not start beyond the new limit.
+        if inner.next_serial > count {
+            inner.next_serial = 1;
+        }
+        Ok(())
+    }
+
+    /// Checks the interface version with the ViGEmBus driver and returns the version it
+    /// accepted.
+    ///
+    /// ViGEmBus never reports its own version, it only accepts or rejects the one sent by
+    /// this crate. On success, the returned value is therefore always the interface
+    /// version this crate was built against, which can be printed in diagnostics.
+    ///
+    /// # Errors
+    /// Returns a [`ClientError::BusError`] if the driver rejects the version or the
+    /// request fails, e.g. because the driver was restarted.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// println!("ViGEmBus interface version: {:#06x}", client.driver_version().unwrap());
+    /// ```
+    pub fn driver_version(&self) -> Result<u32, ClientError> {
+        let inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+        let version = inner.bus.driver_version()?;
+        Ok(version)
+    }
+
+    /// Verifies that the connection to the bus is still usable.
+    ///
+    /// This issues the same cheap version query as [`driver_version`](Client::driver_version)
+    /// and discards the result. An error means the handle or the driver is in a bad state,
+    /// e.g. because the driver was restarted, and the `Client` should be recreated.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::{thread, time::Duration};
+    /// let client = Client::connect().unwrap();
+    ///
+    /// loop {
+    ///     if let Err(e) = client.health_check() {
+    ///         eprintln!("ViGEm bus connection lost: {e}");
+    ///         break;
+    ///     }
+    ///     thread::sleep(Duration::from_secs(5));
+    /// }
+    /// ```
+    pub fn health_check(&self) -> Result<(), ClientError> {
+        let inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+        inner.bus.driver_version()?;
+        Ok(())
+    }
+
+    /// Returns a snapshot of the activity counters of this client.
+    ///
+    /// Counting has to be enabled with [`ClientBuilder::with_metrics`]; otherwise all
+    /// counters stay at zero. Only successful operations are counted.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Report};
+    /// let client = Client::builder().with_metrics().connect().unwrap();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    /// x360.update(&X360Report::default()).unwrap();
+    ///
+    /// let metrics = client.metrics();
+    /// assert_eq!(metrics.plug_count, 1);
+    /// assert_eq!(metrics.reports_submitted, 1);
+    /// ```
+    pub fn metrics(&self) -> Metrics {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner
+            .bus
+            .metrics()
+            .map(|counters| counters.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Returns the Win32 device interface path of the ViGEm bus this client is connected to.
+    ///
+    /// This is the path discovered through `SetupDiGetDeviceInterfaceDetailW` and opened
+    /// during [`connect`](Client::connect), e.g. for correlating the connection with
+    /// Device Manager entries or handing it to other tools that talk to ViGEmBus.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// println!("Connected to {}", client.device_path());
+    /// ```
+    pub fn device_path(&self) -> String {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.bus.device_path().to_owned()
+    }
+
+    #[cfg(feature = "x360")]
+    /// Submits reports to several Xbox 360 targets under a single lock of the client.
+    ///
+    /// Each entry pairs a target's serial number (see
+    /// [`plugin_with_serial`](TargetBuilder::plugin_with_serial)) with the report to send.
+    /// All serials are validated before anything is submitted, so an unknown serial fails
+    /// the whole batch up front. Submission stops at the first bus error.
+    ///
+    /// Reports sent this way bypass the handles, so they are not tracked as the last
+    /// report for [`TargetHandle::update_with`].
+    ///
+    /// # Errors
+    /// Returns [`ClientError::TargetDoesNotExist`] if a serial doesn't belong to an
+    /// Xbox 360 target plugged in by this client.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Button, X360Report};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::connect()?;
+    /// let (_p1, serial_1) = client.new_x360_target().plugin_with_serial()?;
+    /// let (_p2, serial_2) = client.new_x360_target().plugin_with_serial()?;
+    ///
+    /// let jump = X360Report::builder().buttons(X360Button::A).build();
+    /// client.update_x360_many(&[(serial_1, jump), (serial_2, jump)])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_x360_many(&self, updates: &[(u32, X360Report)]) -> Result<(), ClientError> {
+        let inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+
+        for (serial_no, _) in updates {
+            match inner.targets.get(serial_no) {
+                Some(target) if target.kind == TargetType::Xbox360 => {}
+                _ => return Err(ClientError::TargetDoesNotExist(*serial_no)),
+            }
+        }
+
+        for (serial_no, report) in updates {
+            inner.bus.update_x360(*serial_no, report)?;
+        }
+
+        Ok(())
+    }
+
+    /// Unplugs every virtual controller managed by this client.
+    ///
+    /// This gives deterministic teardown without dropping the `Client` or every
+    /// [`TargetHandle`]. All targets are removed from the client even if unplugging
+    /// some of them fails; the first error encountered is returned.
+    ///
+    /// Any existing [`TargetHandle`]s will report the target as no longer existing.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    /// let ds4 = client.new_ds4_target().plugin().unwrap();
+    ///
+    /// // Unplug both controllers at once.
+    /// client.unplug_all().unwrap();
+    /// ```
+    pub fn unplug_all(&self) -> Result<(), ClientError> {
+        let mut inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+        let mut first_error = None;
+
+        for serial_no in inner.targets.keys() {
+            if let Err(e) = inner.bus.unplug(*serial_no) {
+                first_error.get_or_insert(e);
+            }
+        }
+        inner.targets.clear();
+
+        match first_error {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Registers a callback for unplug failures that can't be returned to the caller.
+    ///
+    /// Targets are unplugged implicitly when the last [`TargetHandle`] or the `Client`
+    /// is dropped, and by [`TargetHandle::unplug`]. If the driver fails to remove the
+    /// device on one of these paths, the virtual controller may be leaked. The callback
+    /// receives the serial number of the affected target and the error, e.g. for logging.
+    ///
+    /// Registering a new callback replaces the previous one. The callback runs while the
+    /// client is locked, so it must not call back into the `Client` or its targets.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// client.on_unplug_error(|serial_no, error| {
+    ///     eprintln!("Failed to unplug target {serial_no}: {error}");
+    /// });
+    /// ```
+    pub fn on_unplug_error(&self, f: impl Fn(u32, BusError) + Send + 'static) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.unplug_error_handler = Some(Box::new(f));
+    }
+
+    /// Lists all virtual controllers currently attached to the ViGEm bus, including
+    /// those created by other processes or other `Client`s.
+    ///
+    /// The driver itself can't be queried for its targets, so this inspects the child
+    /// devices of the bus through the Windows configuration manager. The result is a
+    /// snapshot and may already be outdated when it is returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    ///
+    /// for target in client.enumerate_system_targets().unwrap() {
+    ///     if !target.owned {
+    ///         println!("Foreign target {:?} in slot {}", target.kind, target.serial_no);
+    ///     }
+    /// }
+    /// ```
+    pub fn enumerate_system_targets(&self) -> Result<Vec<SystemTargetInfo>, ClientError> {
+        // Walking the bus can be slow, so it runs without holding the client lock.
+        let bus = self
+            .inner
+            .lock()
+            .map_err(|_| ClientError::Poisoned)?
+            .bus
+            .clone();
+        let children = bus.enumerate_children()?;
+        let inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+
+        Ok(children
+            .into_iter()
+            .map(|child| SystemTargetInfo {
+                serial_no: child.serial_no,
+                kind: child.kind.map(ControllerKind::from),
+                vendor_id: child.vendor_id,
+                product_id: child.product_id,
+                owned: inner.targets.contains_key(&child.serial_no),
+            })
+            .collect())
+    }
+
+    pub(crate) fn plugin_internal<T: TargetKind>(
         &self,
         target: Target,
+        requested_serial: Option<u32>,
     ) -> Result<TargetHandle<T>, ClientError> {
-        let mut inner = self.inner.lock().expect("Client mutex was poisoned");
-        let mut target = target;
-
-        for serial_no in 1..=inner.max_targets {
-            if !inner.targets.contains_key(&serial_no) && inner.bus.plug(&target, serial_no).is_ok()
-            {
-                target.serial_no = serial_no;
-                inner.targets.insert(serial_no, target);
-
-                return Ok(TargetHandle::new(
-                    serial_no,
-                    inner.bus.clone(),
-                    Arc::downgrade(&self.inner),
-                ));
+        // A zero VID or PID makes device enumeration fail in ways that only show up as a
+        // generic plug failure, so reject it before it reaches the driver.
+        if target.vendor_id == 0 || target.product_id == 0 {
+            return Err(ClientError::InvalidIdentifiers(
+                target.vendor_id,
+                target.product_id,
+            ));
+        }
+
+        let mut inner = self.inner.lock().map_err(|_| ClientError::Poisoned)?;
+
+        // An out-of-range serial would otherwise surface as `NoFreeSlot` or reach the
+        // driver, so reject it before plugging anything in.
+        if let Some(serial_no) = requested_serial
+            && !(1..=inner.max_targets).contains(&serial_no)
+        {
+            return Err(ClientError::InvalidSerial(serial_no, inner.max_targets));
+        }
+
+        let serial_no = inner.plug(target, requested_serial)?;
+
+        Ok(TargetHandle::new(
+            serial_no,
+            inner.bus.clone(),
+            Arc::downgrade(&self.inner),
+        ))
+    }
+}
+
+impl ClientInner {
+    /// Plugs `target` into the first free slot (or only `requested_serial`) and
+    /// starts tracking it, returning the assigned serial number.
+    pub(crate) fn plug(
+        &mut self,
+        mut target: Target,
+        requested_serial: Option<u32>,
+    ) -> Result<u32, ClientError> {
+        // An explicitly requested serial skips the scan and only tries that single slot.
+        // Otherwise the scan starts at `start` and wraps around to cover every slot once.
+        let candidates = match requested_serial {
+            Some(serial_no) => (serial_no..=serial_no).chain(1..1),
+            None => {
+                let start = match self.serial_strategy {
+                    SerialStrategy::LowestFree => 1,
+                    SerialStrategy::Monotonic => self.next_serial,
+                };
+                (start..=self.max_targets).chain(1..start)
+            }
+        };
+
+        for serial_no in candidates {
+            if self.targets.contains_key(&serial_no) {
+                continue;
+            }
+
+            match self.bus.plug(&target, serial_no) {
+                Ok(()) => {
+                    target.serial_no = serial_no;
+                    self.targets.insert(serial_no, target);
+                    if requested_serial.is_none() {
+                        self.next_serial = serial_no % self.max_targets + 1;
+                    }
+                    return Ok(serial_no);
+                }
+                // The slot is in use, e.g. by another client. Try the next one.
+                Err(e) if e.is_slot_in_use() => {
+                    trace!("Serial {serial_no} is already in use, trying the next one");
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Plugging in a target with serial {serial_no} failed: {e}");
+                    return Err(ClientError::PluginFailed(serial_no, e));
+                }
             }
         }
 
+        warn!("No free serial left to plug in a target");
         Err(ClientError::NoFreeSlot)
     }
+
+    /// Unplugs `serial_no` on a path that can't return an error, passing any failure
+    /// to the handler registered through [`Client::on_unplug_error`].
+    pub(crate) fn unplug_reporting(&self, serial_no: u32) {
+        if let Err(e) = self.bus.unplug(serial_no)
+            && let Some(handler) = &self.unplug_error_handler
+        {
+            handler(serial_no, e);
+        }
+    }
 }
 
 impl Drop for ClientInner {
     fn drop(&mut self) {
         for target in self.targets.values() {
-            let _ = self.bus.unplug(target.serial_no);
+            self.unplug_reporting(target.serial_no);
         }
         self.targets.clear();
     }
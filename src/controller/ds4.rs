@@ -1,8 +1,20 @@
 use bitflags::bitflags;
 use std::{
-    mem,
+    fmt, mem,
     ops::{Deref, DerefMut},
+    ptr,
+    str::FromStr,
 };
+use thiserror::Error;
+
+use crate::client::ClientError;
+#[cfg(feature = "x360")]
+use crate::controller::x360::{X360Button, X360Report};
+use crate::controller::{
+    ControllerReport, Dpad8, ParseButtonError, Rumble, fmt_buttons, normalized_trigger,
+    parse_buttons,
+};
+use crate::target::{DualShock4, TargetHandle};
 
 bitflags! {
     /// Represents the main digital buttons on a virtual DualShock 4 controller.
@@ -38,6 +50,89 @@ bitflags! {
     }
 }
 
+impl Ds4Button {
+    /// Combines a slice of buttons into a single set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Button;
+    ///
+    /// let buttons = Ds4Button::from_slice(&[Ds4Button::CROSS, Ds4Button::SHOULDER_LEFT]);
+    /// assert_eq!(buttons, Ds4Button::CROSS | Ds4Button::SHOULDER_LEFT);
+    /// assert!(Ds4Button::from_slice(&[]).is_empty());
+    /// ```
+    #[inline]
+    pub fn from_slice(buttons: &[Ds4Button]) -> Self {
+        buttons.iter().copied().collect()
+    }
+
+    /// Returns the names of the pressed buttons, in flag definition order.
+    ///
+    /// Bits without a named flag are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Button;
+    ///
+    /// let buttons = Ds4Button::CROSS | Ds4Button::SHOULDER_LEFT;
+    /// assert_eq!(buttons.names(), ["SHOULDER_LEFT", "CROSS"]);
+    /// ```
+    pub fn names(&self) -> Vec<&'static str> {
+        self.iter_names().map(|(name, _)| name).collect()
+    }
+
+    /// Returns an iterator over the individual pressed buttons, in flag definition order.
+    ///
+    /// Each item is a single named flag, e.g. for rendering a list of pressed buttons.
+    /// Bits without a named flag are skipped.
+    /// The D-Pad is not a set of flags and therefore never yielded, see [`Ds4Dpad`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Button;
+    ///
+    /// let buttons = Ds4Button::CROSS | Ds4Button::SHOULDER_LEFT;
+    /// let pressed: Vec<_> = buttons.pressed_iter().collect();
+    /// assert_eq!(pressed, [Ds4Button::SHOULDER_LEFT, Ds4Button::CROSS]);
+    /// ```
+    pub fn pressed_iter(&self) -> impl Iterator<Item = Ds4Button> + use<> {
+        self.iter_names().map(|(_, button)| button)
+    }
+}
+
+impl FromStr for Ds4Button {
+    type Err = ParseButtonError;
+
+    /// Parses a comma-separated list of button names, e.g. "CROSS, SHOULDER_LEFT".
+    ///
+    /// Names match the flag constants and are case-insensitive. Bits without a name
+    /// can be given in hexadecimal (`0x0800`). An empty string yields no buttons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Button;
+    ///
+    /// let buttons: Ds4Button = "CROSS, SHOULDER_LEFT".parse().unwrap();
+    /// assert_eq!(buttons, Ds4Button::CROSS | Ds4Button::SHOULDER_LEFT);
+    /// assert_eq!(buttons.to_string(), "SHOULDER_LEFT, CROSS");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_buttons(s)
+    }
+}
+
+impl fmt::Display for Ds4Button {
+    /// Formats the pressed buttons as a comma-separated list of names, which
+    /// [`FromStr`] accepts again.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_buttons(self, f)
+    }
+}
+
 bitflags! {
     /// Represents the special buttons (PS, Touchpad) on a virtual DualShock 4 controller.
     #[repr(transparent)]
@@ -67,6 +162,156 @@ pub enum Ds4Dpad {
     Neutral = 8,
 }
 
+impl Ds4Dpad {
+    /// The minimum vector magnitude [`from_vector`](Self::from_vector) treats as a direction.
+    pub const VECTOR_THRESHOLD: f32 = 0.5;
+
+    /// Buckets an angle into one of the eight D-Pad directions.
+    ///
+    /// The angle is in degrees, measured clockwise from North (up), so `90.0` is East.
+    /// Each direction covers a 45° sector centered on it; a value exactly on a sector
+    /// boundary belongs to the next direction clockwise. Angles outside `0..360` wrap
+    /// around, and a non-finite angle yields [`Ds4Dpad::Neutral`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Dpad;
+    ///
+    /// assert_eq!(Ds4Dpad::from_angle(0.0), Ds4Dpad::North);
+    /// assert_eq!(Ds4Dpad::from_angle(22.4), Ds4Dpad::North);
+    /// assert_eq!(Ds4Dpad::from_angle(22.5), Ds4Dpad::NorthEast);
+    /// assert_eq!(Ds4Dpad::from_angle(90.0), Ds4Dpad::East);
+    /// assert_eq!(Ds4Dpad::from_angle(337.5), Ds4Dpad::North);
+    /// assert_eq!(Ds4Dpad::from_angle(-90.0), Ds4Dpad::West);
+    /// assert_eq!(Ds4Dpad::from_angle(f32::NAN), Ds4Dpad::Neutral);
+    /// ```
+    pub fn from_angle(degrees: f32) -> Ds4Dpad {
+        if !degrees.is_finite() {
+            return Ds4Dpad::Neutral;
+        }
+
+        let sector = ((degrees + 22.5).rem_euclid(360.0) / 45.0) as u8;
+        match sector {
+            0 => Ds4Dpad::North,
+            1 => Ds4Dpad::NorthEast,
+            2 => Ds4Dpad::East,
+            3 => Ds4Dpad::SouthEast,
+            4 => Ds4Dpad::South,
+            5 => Ds4Dpad::SouthWest,
+            6 => Ds4Dpad::West,
+            7 => Ds4Dpad::NorthWest,
+            // Only reachable through floating point rounding right below 360°.
+            _ => Ds4Dpad::North,
+        }
+    }
+
+    /// Converts an analog direction into one of the eight D-Pad directions.
+    ///
+    /// `x` grows to the right and `y` grows upwards, both normally in the range `-1.0..=1.0`.
+    /// Vectors shorter than [`VECTOR_THRESHOLD`](Self::VECTOR_THRESHOLD) yield
+    /// [`Ds4Dpad::Neutral`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Dpad;
+    ///
+    /// assert_eq!(Ds4Dpad::from_vector(0.0, 1.0), Ds4Dpad::North);
+    /// assert_eq!(Ds4Dpad::from_vector(0.7, -0.7), Ds4Dpad::SouthEast);
+    /// assert_eq!(Ds4Dpad::from_vector(-1.0, 0.0), Ds4Dpad::West);
+    /// assert_eq!(Ds4Dpad::from_vector(0.1, 0.2), Ds4Dpad::Neutral);
+    /// ```
+    pub fn from_vector(x: f32, y: f32) -> Ds4Dpad {
+        if x.hypot(y) < Self::VECTOR_THRESHOLD {
+            return Ds4Dpad::Neutral;
+        }
+
+        Self::from_angle(x.atan2(y).to_degrees())
+    }
+}
+
+/// The error returned when converting an out-of-range value into a [`Ds4Dpad`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Invalid D-Pad value: {0}, expected 0-8")]
+pub struct InvalidDpadValue(pub u8);
+
+impl TryFrom<u8> for Ds4Dpad {
+    type Error = InvalidDpadValue;
+
+    /// Converts the raw D-Pad value, as stored in the low 4 bits of
+    /// [`Ds4Report::buttons`], back into a direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::Ds4Dpad;
+    ///
+    /// assert_eq!(Ds4Dpad::try_from(2), Ok(Ds4Dpad::East));
+    /// assert_eq!(Ds4Dpad::try_from(8), Ok(Ds4Dpad::Neutral));
+    /// assert!(Ds4Dpad::try_from(9).is_err());
+    /// ```
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Ds4Dpad::North),
+            1 => Ok(Ds4Dpad::NorthEast),
+            2 => Ok(Ds4Dpad::East),
+            3 => Ok(Ds4Dpad::SouthEast),
+            4 => Ok(Ds4Dpad::South),
+            5 => Ok(Ds4Dpad::SouthWest),
+            6 => Ok(Ds4Dpad::West),
+            7 => Ok(Ds4Dpad::NorthWest),
+            8 => Ok(Ds4Dpad::Neutral),
+            _ => Err(InvalidDpadValue(value)),
+        }
+    }
+}
+
+impl From<Dpad8> for Ds4Dpad {
+    /// Converts a shared D-Pad direction into the DualShock 4 one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{Dpad8, Ds4Dpad, Ds4Report};
+    ///
+    /// let mut report = Ds4Report::default();
+    /// report.set_dpad(Dpad8::SouthEast.into());
+    ///
+    /// assert_eq!(report.dpad(), Ds4Dpad::SouthEast);
+    /// assert_eq!(Dpad8::from(report.dpad()), Dpad8::SouthEast);
+    /// ```
+    fn from(dpad: Dpad8) -> Self {
+        match dpad {
+            Dpad8::North => Ds4Dpad::North,
+            Dpad8::NorthEast => Ds4Dpad::NorthEast,
+            Dpad8::East => Ds4Dpad::East,
+            Dpad8::SouthEast => Ds4Dpad::SouthEast,
+            Dpad8::South => Ds4Dpad::South,
+            Dpad8::SouthWest => Ds4Dpad::SouthWest,
+            Dpad8::West => Ds4Dpad::West,
+            Dpad8::NorthWest => Ds4Dpad::NorthWest,
+            Dpad8::Neutral => Ds4Dpad::Neutral,
+        }
+    }
+}
+
+impl From<Ds4Dpad> for Dpad8 {
+    fn from(dpad: Ds4Dpad) -> Self {
+        match dpad {
+            Ds4Dpad::North => Dpad8::North,
+            Ds4Dpad::NorthEast => Dpad8::NorthEast,
+            Ds4Dpad::East => Dpad8::East,
+            Ds4Dpad::SouthEast => Dpad8::SouthEast,
+            Ds4Dpad::South => Dpad8::South,
+            Ds4Dpad::SouthWest => Dpad8::SouthWest,
+            Ds4Dpad::West => Dpad8::West,
+            Ds4Dpad::NorthWest => Dpad8::NorthWest,
+            Ds4Dpad::Neutral => Dpad8::Neutral,
+        }
+    }
+}
+
 /// Represents the standard input state of a virtual DualShock 4 controller.
 ///
 /// An instance of this struct is sent to the bus via `TargetHandle::update` to
@@ -93,7 +338,7 @@ pub enum Ds4Dpad {
 /// report.trigger_r = 255;
 /// ```
 #[repr(C)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Ds4Report {
     /// Left thumbstick X-axis (0-255). 128 is center.
     pub thumb_lx: u8,
@@ -138,6 +383,188 @@ impl Ds4Report {
         const DPAD_MASK: u16 = 0x000F;
         self.buttons = (self.buttons & !DPAD_MASK) | (dpad as u16);
     }
+
+    /// Presses or releases a special button (PS, Touchpad) on the report.
+    ///
+    /// This helper manipulates the `special` field, leaving the other special
+    /// buttons untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Report, Ds4SpecialButton};
+    ///
+    /// let mut report = Ds4Report::default();
+    /// report.set_special(Ds4SpecialButton::PS, true);
+    /// report.set_special(Ds4SpecialButton::TOUCHPAD, true);
+    /// report.set_special(Ds4SpecialButton::PS, false);
+    ///
+    /// assert_eq!(report.special_buttons(), Ds4SpecialButton::TOUCHPAD);
+    /// ```
+    #[inline]
+    pub fn set_special(&mut self, button: Ds4SpecialButton, pressed: bool) {
+        let mut special = self.special_buttons();
+        special.set(button, pressed);
+        self.special = special.bits();
+    }
+
+    /// Returns the special buttons (PS, Touchpad) currently pressed on the report.
+    #[inline]
+    pub fn special_buttons(&self) -> Ds4SpecialButton {
+        Ds4SpecialButton::from_bits_retain(self.special)
+    }
+
+    /// Returns the D-Pad state encoded in the lower 4 bits of the `buttons` field.
+    ///
+    /// This is the inverse of [`set_dpad`](Self::set_dpad). Values outside the
+    /// valid range are treated as [`Ds4Dpad::Neutral`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Dpad, Ds4Report};
+    ///
+    /// let mut report = Ds4Report::default();
+    /// assert_eq!(report.dpad(), Ds4Dpad::Neutral);
+    ///
+    /// report.set_dpad(Ds4Dpad::SouthWest);
+    /// assert_eq!(report.dpad(), Ds4Dpad::SouthWest);
+    /// ```
+    #[inline]
+    pub fn dpad(&self) -> Ds4Dpad {
+        Ds4Dpad::try_from((self.buttons & 0x000F) as u8).unwrap_or(Ds4Dpad::Neutral)
+    }
+
+    /// Returns the left thumbstick position normalized to `-1.0..=1.0` on both axes.
+    ///
+    /// The axes keep the orientation of the raw fields: `0.0` is center, negative X
+    /// is left and negative Y is up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4Report;
+    ///
+    /// let report = Ds4Report::builder().left_stick(255, 0).build();
+    /// assert_eq!(report.left_stick(), (1.0, -1.0));
+    /// assert_eq!(Ds4Report::default().left_stick(), (0.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn left_stick(&self) -> (f32, f32) {
+        (
+            normalize_stick_axis(self.thumb_lx),
+            normalize_stick_axis(self.thumb_ly),
+        )
+    }
+
+    /// Returns the right thumbstick position normalized to `-1.0..=1.0` on both axes.
+    ///
+    /// See [`left_stick`](Self::left_stick) for the axis orientation.
+    #[inline]
+    pub fn right_stick(&self) -> (f32, f32) {
+        (
+            normalize_stick_axis(self.thumb_rx),
+            normalize_stick_axis(self.thumb_ry),
+        )
+    }
+
+    /// Returns the left (L2) trigger value normalized to `0.0..=1.0`.
+    #[inline]
+    pub fn left_trigger(&self) -> f32 {
+        self.trigger_l as f32 / 255.0
+    }
+
+    /// Returns the right (R2) trigger value normalized to `0.0..=1.0`.
+    #[inline]
+    pub fn right_trigger(&self) -> f32 {
+        self.trigger_r as f32 / 255.0
+    }
+
+    /// Returns `true` if any field differs from `other`.
+    ///
+    /// Useful for only submitting a report when the input actually changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4Report;
+    ///
+    /// let last_sent = Ds4Report::default();
+    /// let mut report = last_sent;
+    /// assert!(!report.changed_from(&last_sent));
+    ///
+    /// report.trigger_r = 255;
+    /// assert!(report.changed_from(&last_sent));
+    /// ```
+    #[inline]
+    pub fn changed_from(&self, other: &Ds4Report) -> bool {
+        self != other
+    }
+}
+
+/// Maps `-1.0..=1.0` to a raw DS4 stick axis (128 is center). NaN maps to the center.
+fn normalized_to_ds4_axis(value: f32) -> u8 {
+    (128.0 + value.clamp(-1.0, 1.0) * 127.0).round() as u8
+}
+
+impl Ds4Report {
+    /// Sets or clears a single main button, keeping the D-Pad bits intact.
+    fn set_button(&mut self, button: Ds4Button, pressed: bool) {
+        let mut buttons = Ds4Button::from_bits_retain(self.buttons);
+        buttons.set(button, pressed);
+        self.buttons = buttons.bits();
+    }
+}
+
+impl ControllerReport for Ds4Report {
+    type Kind = DualShock4;
+
+    fn set_left_stick_normalized(&mut self, x: f32, y: f32) {
+        // The DS4 Y axis points down, the trait's points up.
+        self.thumb_lx = normalized_to_ds4_axis(x);
+        self.thumb_ly = normalized_to_ds4_axis(-y);
+    }
+
+    fn set_right_stick_normalized(&mut self, x: f32, y: f32) {
+        self.thumb_rx = normalized_to_ds4_axis(x);
+        self.thumb_ry = normalized_to_ds4_axis(-y);
+    }
+
+    /// Also sets the digital L2/R2 buttons while the respective trigger is pulled, like
+    /// a physical DS4 does.
+    fn set_triggers_normalized(&mut self, left: f32, right: f32) {
+        self.trigger_l = normalized_trigger(left);
+        self.trigger_r = normalized_trigger(right);
+        self.set_button(Ds4Button::TRIGGER_LEFT, self.trigger_l > 0);
+        self.set_button(Ds4Button::TRIGGER_RIGHT, self.trigger_r > 0);
+    }
+
+    fn set_button_a(&mut self, pressed: bool) {
+        self.set_button(Ds4Button::CROSS, pressed);
+    }
+
+    fn set_button_b(&mut self, pressed: bool) {
+        self.set_button(Ds4Button::CIRCLE, pressed);
+    }
+
+    fn set_button_x(&mut self, pressed: bool) {
+        self.set_button(Ds4Button::SQUARE, pressed);
+    }
+
+    fn set_button_y(&mut self, pressed: bool) {
+        self.set_button(Ds4Button::TRIANGLE, pressed);
+    }
+
+    fn submit(&self, handle: &TargetHandle<DualShock4>) -> Result<(), ClientError> {
+        handle.update(self)
+    }
+}
+
+/// Maps a raw DS4 stick axis (128 is center) to `-1.0..=1.0`.
+///
+/// The raw range is asymmetric around center, so `0` is clamped to `-1.0`.
+fn normalize_stick_axis(value: u8) -> f32 {
+    ((value as f32 - 128.0) / 127.0).max(-1.0)
 }
 
 impl Default for Ds4Report {
@@ -157,6 +584,313 @@ impl Default for Ds4Report {
     }
 }
 
+/// Converts an Xbox 360 report into the equivalent DualShock 4 report.
+///
+/// The mapping is as follows:
+///
+/// | Xbox 360                        | DualShock 4                       |
+/// |---------------------------------|-----------------------------------|
+/// | `A` / `B` / `X` / `Y`           | Cross / Circle / Square / Triangle |
+/// | `LEFT_SHOULDER` / `RIGHT_SHOULDER` | L1 / R1                        |
+/// | `left_trigger` / `right_trigger` | L2 / R2 (analog, plus the digital bit when non-zero) |
+/// | `LEFT_THUMB` / `RIGHT_THUMB`    | L3 / R3                           |
+/// | `BACK` / `START`                | Share / Options                   |
+/// | `GUIDE`                         | PS                                |
+/// | `DPAD_*`                        | [`Ds4Dpad`] (opposite directions cancel out) |
+/// | Thumbsticks (`i16`, up is positive) | Thumbsticks (`u8`, 128 is center, up is `0`) |
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::{Ds4Button, Ds4Dpad, Ds4Report, X360Button, X360Report};
+///
+/// let mut x360 = X360Report::default();
+/// x360.buttons = X360Button::A | X360Button::DPAD_UP | X360Button::DPAD_RIGHT;
+/// x360.thumb_ly = i16::MAX;
+///
+/// let ds4 = Ds4Report::from(&x360);
+///
+/// assert_eq!(ds4.buttons, Ds4Button::CROSS.bits() | Ds4Dpad::NorthEast as u16);
+/// assert_eq!(ds4.thumb_lx, 128);
+/// assert_eq!(ds4.thumb_ly, 0);
+/// ```
+#[cfg(feature = "x360")]
+impl From<&X360Report> for Ds4Report {
+    fn from(x360: &X360Report) -> Self {
+        const BUTTON_MAP: [(X360Button, Ds4Button); 10] = [
+            (X360Button::A, Ds4Button::CROSS),
+            (X360Button::B, Ds4Button::CIRCLE),
+            (X360Button::X, Ds4Button::SQUARE),
+            (X360Button::Y, Ds4Button::TRIANGLE),
+            (X360Button::LEFT_SHOULDER, Ds4Button::SHOULDER_LEFT),
+            (X360Button::RIGHT_SHOULDER, Ds4Button::SHOULDER_RIGHT),
+            (X360Button::LEFT_THUMB, Ds4Button::THUMB_LEFT),
+            (X360Button::RIGHT_THUMB, Ds4Button::THUMB_RIGHT),
+            (X360Button::BACK, Ds4Button::SHARE),
+            (X360Button::START, Ds4Button::OPTIONS),
+        ];
+
+        let mut buttons = Ds4Button::empty();
+        for (x360_button, ds4_button) in BUTTON_MAP {
+            buttons.set(ds4_button, x360.buttons.contains(x360_button));
+        }
+        buttons.set(Ds4Button::TRIGGER_LEFT, x360.left_trigger > 0);
+        buttons.set(Ds4Button::TRIGGER_RIGHT, x360.right_trigger > 0);
+
+        let mut report = Self {
+            thumb_lx: stick_axis_to_ds4(x360.thumb_lx),
+            thumb_ly: stick_axis_to_ds4(x360.thumb_ly.saturating_neg()),
+            thumb_rx: stick_axis_to_ds4(x360.thumb_rx),
+            thumb_ry: stick_axis_to_ds4(x360.thumb_ry.saturating_neg()),
+            buttons: buttons.bits(),
+            special: 0,
+            trigger_l: x360.left_trigger,
+            trigger_r: x360.right_trigger,
+        };
+        report.set_special(
+            Ds4SpecialButton::PS,
+            x360.buttons.contains(X360Button::GUIDE),
+        );
+        report.set_dpad(dpad_from_x360(x360.buttons));
+        report
+    }
+}
+
+/// Rescales a signed Xbox 360 stick axis (0 is center) to a DS4 axis (128 is center).
+#[cfg(feature = "x360")]
+#[inline]
+fn stick_axis_to_ds4(value: i16) -> u8 {
+    ((value as i32 + 32768) >> 8) as u8
+}
+
+/// Collapses the Xbox 360 D-Pad flags into a single DS4 D-Pad direction.
+#[cfg(feature = "x360")]
+fn dpad_from_x360(buttons: X360Button) -> Ds4Dpad {
+    let up = buttons.contains(X360Button::DPAD_UP);
+    let down = buttons.contains(X360Button::DPAD_DOWN);
+    let left = buttons.contains(X360Button::DPAD_LEFT);
+    let right = buttons.contains(X360Button::DPAD_RIGHT);
+
+    // Opposite directions cancel each other out.
+    match (up && !down, down && !up, left && !right, right && !left) {
+        (true, _, _, true) => Ds4Dpad::NorthEast,
+        (true, _, true, _) => Ds4Dpad::NorthWest,
+        (true, _, _, _) => Ds4Dpad::North,
+        (_, true, _, true) => Ds4Dpad::SouthEast,
+        (_, true, true, _) => Ds4Dpad::SouthWest,
+        (_, true, _, _) => Ds4Dpad::South,
+        (_, _, _, true) => Ds4Dpad::East,
+        (_, _, true, _) => Ds4Dpad::West,
+        _ => Ds4Dpad::Neutral,
+    }
+}
+
+impl Ds4Report {
+    /// Creates a builder to construct a report with chained calls.
+    ///
+    /// The builder starts from [`Ds4Report::default()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Button, Ds4Dpad, Ds4Report};
+    ///
+    /// let report = Ds4Report::builder()
+    ///     .buttons(Ds4Button::CROSS)
+    ///     .dpad(Ds4Dpad::South)
+    ///     .right_trigger(255)
+    ///     .build();
+    ///
+    /// assert_eq!(report.buttons, Ds4Button::CROSS.bits() | Ds4Dpad::South as u16);
+    /// assert_eq!(report.trigger_r, 255);
+    /// ```
+    #[inline]
+    pub fn builder() -> Ds4ReportBuilder {
+        Ds4ReportBuilder::default()
+    }
+
+    /// Returns a report with no buttons pressed, the D-Pad neutral, both triggers
+    /// released and both sticks centered.
+    ///
+    /// This is the same as [`Ds4Report::default()`], but spells out the intent when
+    /// returning the controller to rest, e.g. `*report = Ds4Report::neutral()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Dpad, Ds4Report};
+    ///
+    /// let report = Ds4Report::neutral();
+    /// assert_eq!(report.dpad(), Ds4Dpad::Neutral);
+    /// assert_eq!((report.trigger_l, report.thumb_lx), (0, 128));
+    /// ```
+    #[inline]
+    pub fn neutral() -> Self {
+        Self::default()
+    }
+
+    /// Releases all buttons, including the special buttons and the D-Pad, releases both
+    /// triggers and centers both sticks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Button, Ds4Dpad, Ds4Report};
+    ///
+    /// let mut report = Ds4Report::builder()
+    ///     .buttons(Ds4Button::CROSS)
+    ///     .dpad(Ds4Dpad::North)
+    ///     .right_trigger(255)
+    ///     .left_stick(255, 0)
+    ///     .build();
+    /// report.all_released();
+    ///
+    /// assert_eq!(report, Ds4Report::neutral());
+    /// ```
+    #[inline]
+    pub fn all_released(&mut self) {
+        *self = Self::neutral();
+    }
+
+    /// Decodes a report from its raw 9-byte `DS4_REPORT` wire format.
+    ///
+    /// The layout is the four thumbstick axes (1 byte each), the little-endian button
+    /// mask (2 bytes), the special buttons and both triggers (1 byte each). Unlike the
+    /// in-memory layout of `Ds4Report`, it has no trailing padding byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Dpad, Ds4Report};
+    ///
+    /// let report = Ds4Report::from_bytes(&[128, 128, 255, 128, 0x28, 0x00, 0, 0, 255]);
+    ///
+    /// assert_eq!(report.thumb_rx, 255);
+    /// assert_eq!(report.dpad(), Ds4Dpad::Neutral);
+    /// assert_eq!(report.trigger_r, 255);
+    /// ```
+    pub fn from_bytes(bytes: &[u8; 9]) -> Self {
+        Self {
+            thumb_lx: bytes[0],
+            thumb_ly: bytes[1],
+            thumb_rx: bytes[2],
+            thumb_ry: bytes[3],
+            buttons: u16::from_le_bytes([bytes[4], bytes[5]]),
+            special: bytes[6],
+            trigger_l: bytes[7],
+            trigger_r: bytes[8],
+        }
+    }
+
+    /// Encodes the report into its raw 9-byte `DS4_REPORT` wire format.
+    ///
+    /// This is the inverse of [`from_bytes`](Self::from_bytes). The button mask is always
+    /// written as little-endian, so the bytes can be sent to another machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4Report;
+    ///
+    /// let mut report = Ds4Report::default();
+    /// report.trigger_l = 64;
+    ///
+    /// assert_eq!(report.to_bytes(), [128, 128, 128, 128, 0x08, 0x00, 0, 64, 0]);
+    /// assert_eq!(Ds4Report::from_bytes(&report.to_bytes()), report);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 9] {
+        let [buttons_lo, buttons_hi] = self.buttons.to_le_bytes();
+        [
+            self.thumb_lx,
+            self.thumb_ly,
+            self.thumb_rx,
+            self.thumb_ry,
+            buttons_lo,
+            buttons_hi,
+            self.special,
+            self.trigger_l,
+            self.trigger_r,
+        ]
+    }
+
+    /// Decodes a report from a byte slice in the format of [`to_bytes`](Self::to_bytes).
+    ///
+    /// Returns `None` unless the slice is exactly 9 bytes long.
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self::from_bytes)
+    }
+}
+
+/// A builder for [`Ds4Report`].
+///
+/// Obtain one from [`Ds4Report::builder()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ds4ReportBuilder {
+    report: Ds4Report,
+}
+
+impl Ds4ReportBuilder {
+    /// Sets the pressed main buttons, replacing any previously set buttons.
+    ///
+    /// The D-Pad state is left untouched.
+    #[inline]
+    pub fn buttons(mut self, buttons: Ds4Button) -> Self {
+        const DPAD_MASK: u16 = 0x000F;
+        self.report.buttons = (self.report.buttons & DPAD_MASK) | buttons.bits();
+        self
+    }
+
+    /// Sets the D-Pad state.
+    #[inline]
+    pub fn dpad(mut self, dpad: Ds4Dpad) -> Self {
+        self.report.set_dpad(dpad);
+        self
+    }
+
+    /// Sets the pressed special buttons (PS, Touchpad).
+    #[inline]
+    pub fn special(mut self, special: Ds4SpecialButton) -> Self {
+        self.report.special = special.bits();
+        self
+    }
+
+    /// Sets the left trigger value (0-255).
+    #[inline]
+    pub fn left_trigger(mut self, value: u8) -> Self {
+        self.report.trigger_l = value;
+        self
+    }
+
+    /// Sets the right trigger value (0-255).
+    #[inline]
+    pub fn right_trigger(mut self, value: u8) -> Self {
+        self.report.trigger_r = value;
+        self
+    }
+
+    /// Sets the left thumbstick position. 128 is center on both axes.
+    #[inline]
+    pub fn left_stick(mut self, x: u8, y: u8) -> Self {
+        self.report.thumb_lx = x;
+        self.report.thumb_ly = y;
+        self
+    }
+
+    /// Sets the right thumbstick position. 128 is center on both axes.
+    #[inline]
+    pub fn right_stick(mut self, x: u8, y: u8) -> Self {
+        self.report.thumb_rx = x;
+        self.report.thumb_ry = y;
+        self
+    }
+
+    /// Returns the constructed report.
+    #[inline]
+    pub fn build(self) -> Ds4Report {
+        self.report
+    }
+}
+
 // EXTENDED REPORT SECTION
 
 /// Represents a single packet of touchpad data for a DualShock 4 controller.
@@ -221,6 +955,47 @@ impl Ds4Touch {
         (x, y)
     }
 
+    /// Builds a complete touch packet for up to two fingers in one call.
+    ///
+    /// Each finger is given as `(tracking_num, x, y)`, or `None` if that finger is up.
+    /// This is the common shape for pinch and zoom gestures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4Touch;
+    ///
+    /// let touch = Ds4Touch::two_fingers(7, Some((1, 500, 300)), Some((2, 1400, 600)));
+    /// assert_eq!(touch.get_packet_counter(), 7);
+    /// assert!(touch.get_is_down_1() && touch.get_is_down_2());
+    /// assert_eq!(touch.get_coords_2(), (1400, 600));
+    ///
+    /// let lifted = Ds4Touch::two_fingers(8, Some((1, 500, 300)), None);
+    /// assert!(!lifted.get_is_down_2());
+    /// ```
+    #[inline]
+    pub fn two_fingers(
+        counter: u8,
+        f1: Option<(u8, u16, u16)>,
+        f2: Option<(u8, u16, u16)>,
+    ) -> Self {
+        let mut touch = Self {
+            packet_counter: counter,
+            ..Default::default()
+        };
+
+        match f1 {
+            Some((tracking_num, x, y)) => touch.set_touch_1(true, tracking_num, x, y),
+            None => touch.set_touch_1(false, 0, 0, 0),
+        }
+        match f2 {
+            Some((tracking_num, x, y)) => touch.set_touch_2(true, tracking_num, x, y),
+            None => touch.set_touch_2(false, 0, 0, 0),
+        }
+
+        touch
+    }
+
     /// Sets the state for the first touch contact, abstracting away the bit-packing.
     ///
     /// # Arguments
@@ -299,11 +1074,39 @@ impl Ds4Touch {
     }
 }
 
+/// Raw gyroscope units per degree per second, as reported by a real DualShock 4.
+///
+/// The DS4 IMU is configured for a ±2000 °/s range over the signed 16-bit field.
+pub const DS4_GYRO_RES_PER_DEG_S: f32 = 16.0;
+
+/// Raw accelerometer units per g, as reported by a real DualShock 4.
+///
+/// The DS4 IMU is configured for a ±4 g range over the signed 16-bit field.
+pub const DS4_ACCEL_RES_PER_G: f32 = 8192.0;
+
 /// Represents the complete, extended input state of a virtual DualShock 4 controller.
 ///
-/// This struct is used for advanced scenarios that require simulating motion controls
-/// (gyroscope and accelerometer) and detailed touchpad activity. It contains all the
-/// fields from the standard [`Ds4Report`] plus additional data.
+/// This struct is used for advanced scenarios that require simulating motion controls
+/// (gyroscope and accelerometer) and detailed touchpad activity. It contains all the
+/// fields from the standard [`Ds4Report`] plus additional data.
+///
+/// Because the struct is packed, its multi-byte fields may be misaligned, and taking a
+/// reference to them is undefined behavior. Plain reads and assignments are fine, and
+/// accessors such as [`gyro_x`](Self::gyro_x) and [`set_gyro_x`](Self::set_gyro_x) are
+/// provided for all of them when a method is more convenient.
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::ds4::Ds4ReportEx;
+///
+/// let mut report = Ds4ReportEx::default();
+/// report.set_gyro_x(12345);
+/// report.set_accel_y(-8192);
+///
+/// assert_eq!(report.gyro_x(), 12345);
+/// assert_eq!(report.accel_y(), -8192);
+/// ```
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
 pub struct Ds4ReportExData {
@@ -316,6 +1119,7 @@ pub struct Ds4ReportExData {
     pub trigger_l: u8,
     pub trigger_r: u8,
     pub timestamp: u16,
+    /// Raw battery level (0-255). See [`set_battery`](Self::set_battery).
     pub battery_lvl: u8,
     pub gyro_x: i16,
     pub gyro_y: i16,
@@ -324,6 +1128,8 @@ pub struct Ds4ReportExData {
     pub accel_y: i16,
     pub accel_z: i16,
     pub _unknown1: [u8; 5],
+    /// Battery and peripheral status. The low nibble holds the battery level in tenths,
+    /// bit 4 (`0x10`) is set while a cable is connected. See [`set_battery`](Self::set_battery).
     pub battery_lvl_special: u8,
     pub _unknown2: [u8; 2],
     pub touch_packets_n: u8,
@@ -354,21 +1160,187 @@ impl Ds4ReportExData {
     pub fn set_dpad(&mut self, dpad: Ds4Dpad) {
         self.as_report_mut().set_dpad(dpad);
     }
+
+    /// A convenience method to press or release a special button on the extended report.
+    /// It correctly manipulates the `special` field.
+    pub fn set_special(&mut self, button: Ds4SpecialButton, pressed: bool) {
+        self.as_report_mut().set_special(button, pressed);
+    }
+
+    /// Returns the special buttons (PS, Touchpad) currently pressed on the extended report.
+    pub fn special_buttons(&self) -> Ds4SpecialButton {
+        self.as_report().special_buttons()
+    }
+
+    /// Copies the fields shared with the standard [`Ds4Report`] into this extended report.
+    ///
+    /// Motion, touch and battery data are left untouched.
+    #[inline]
+    pub fn apply_report(&mut self, report: &Ds4Report) {
+        self.thumb_lx = report.thumb_lx;
+        self.thumb_ly = report.thumb_ly;
+        self.thumb_rx = report.thumb_rx;
+        self.thumb_ry = report.thumb_ry;
+        self.buttons = report.buttons;
+        self.special = report.special;
+        self.trigger_l = report.trigger_l;
+        self.trigger_r = report.trigger_r;
+    }
+
+    /// Sets the gyroscope fields from angular velocities in degrees per second.
+    ///
+    /// Values are scaled by [`DS4_GYRO_RES_PER_DEG_S`] and saturate at the
+    /// limits of the raw `i16` fields (roughly ±2000 °/s).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4ReportEx;
+    ///
+    /// let mut report = Ds4ReportEx::default();
+    /// report.set_gyro(90.0, 0.0, -45.0);
+    ///
+    /// assert_eq!({ report.gyro_x }, 1440);
+    /// assert_eq!({ report.gyro_z }, -720);
+    /// ```
+    #[inline]
+    pub fn set_gyro(&mut self, x: f32, y: f32, z: f32) {
+        self.gyro_x = (x * DS4_GYRO_RES_PER_DEG_S).round() as i16;
+        self.gyro_y = (y * DS4_GYRO_RES_PER_DEG_S).round() as i16;
+        self.gyro_z = (z * DS4_GYRO_RES_PER_DEG_S).round() as i16;
+    }
+
+    /// Sets the accelerometer fields from accelerations in g-units.
+    ///
+    /// Values are scaled by [`DS4_ACCEL_RES_PER_G`] and saturate at the
+    /// limits of the raw `i16` fields (roughly ±4 g).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4ReportEx;
+    ///
+    /// let mut report = Ds4ReportEx::default();
+    /// // Controller lying flat on a table.
+    /// report.set_accel(0.0, 1.0, 0.0);
+    ///
+    /// assert_eq!({ report.accel_y }, 8192);
+    /// ```
+    #[inline]
+    pub fn set_accel(&mut self, x: f32, y: f32, z: f32) {
+        self.accel_x = (x * DS4_ACCEL_RES_PER_G).round() as i16;
+        self.accel_y = (y * DS4_ACCEL_RES_PER_G).round() as i16;
+        self.accel_z = (z * DS4_ACCEL_RES_PER_G).round() as i16;
+    }
+
+    /// Sets the battery level in percent and whether the controller is charging.
+    ///
+    /// `percent` is clamped to 100. The level is encoded the way a real DualShock 4
+    /// reports it and hosts decode it:
+    ///
+    /// - `battery_lvl` receives the level scaled to `0-255`.
+    /// - The low nibble of `battery_lvl_special` holds the level in tenths. While
+    ///   charging, bit 4 (cable connected) is set and the nibble ranges from `0` to `10`.
+    ///   On battery, hosts read the nibble as one tenth lower, so it ranges from `0`
+    ///   (10%) to `9` (100%).
+    ///
+    /// The remaining bits of `battery_lvl_special` (headphone and microphone state)
+    /// are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4ReportEx;
+    ///
+    /// let mut report = Ds4ReportEx::default();
+    ///
+    /// report.set_battery(80, true);
+    /// assert_eq!(report.battery_lvl_special, 0x10 | 8);
+    ///
+    /// report.set_battery(80, false);
+    /// assert_eq!(report.battery_lvl_special, 7);
+    /// ```
+    #[inline]
+    pub fn set_battery(&mut self, percent: u8, charging: bool) {
+        const CABLE_CONNECTED: u8 = 0x10;
+        const LEVEL_MASK: u8 = 0x0F;
+
+        let percent = percent.min(100);
+        let tenths = percent / 10;
+        let (level, cable) = if charging {
+            (tenths, CABLE_CONNECTED)
+        } else {
+            (tenths.saturating_sub(1), 0)
+        };
+
+        self.battery_lvl = (percent as u16 * 255 / 100) as u8;
+        self.battery_lvl_special =
+            (self.battery_lvl_special & !(LEVEL_MASK | CABLE_CONNECTED)) | cable | level;
+    }
+
+    /// Advances the `timestamp` by `delta` ticks, wrapping around on overflow.
+    ///
+    /// A real DS4 counts in ticks of about 5.33 µs (3 ticks per 16 µs). Some games use
+    /// the timestamp to tell fresh motion data from repeated reports. To derive it from
+    /// the elapsed time automatically, use `Ds4ExUpdater` from the `target` module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4ReportEx;
+    ///
+    /// let mut report = Ds4ReportEx::default();
+    /// report.set_timestamp(u16::MAX);
+    /// report.bump_timestamp(2);
+    /// assert_eq!(report.timestamp(), 1);
+    /// ```
+    #[inline]
+    pub fn bump_timestamp(&mut self, delta: u16) {
+        self.set_timestamp(self.timestamp().wrapping_add(delta));
+    }
+}
+
+/// Generates unaligned getters and setters for multi-byte fields of a packed struct.
+macro_rules! packed_accessors {
+    ($($field:ident, $setter:ident: $ty:ty;)*) => {
+        $(
+            #[doc = concat!("Reads `", stringify!($field), "` without referencing the packed field.")]
+            #[inline]
+            pub fn $field(&self) -> $ty {
+                // SAFETY: The pointer is derived from `self` and valid for reads, and
+                // `read_unaligned` has no alignment requirement.
+                unsafe { ptr::read_unaligned(&raw const self.$field) }
+            }
+
+            #[doc = concat!("Writes `", stringify!($field), "` without referencing the packed field.")]
+            #[inline]
+            pub fn $setter(&mut self, value: $ty) {
+                // SAFETY: The pointer is derived from `self` and valid for writes, and
+                // `write_unaligned` has no alignment requirement.
+                unsafe { ptr::write_unaligned(&raw mut self.$field, value) }
+            }
+        )*
+    };
+}
+
+impl Ds4ReportExData {
+    packed_accessors! {
+        buttons, set_buttons: u16;
+        timestamp, set_timestamp: u16;
+        gyro_x, set_gyro_x: i16;
+        gyro_y, set_gyro_y: i16;
+        gyro_z, set_gyro_z: i16;
+        accel_x, set_accel_x: i16;
+        accel_y, set_accel_y: i16;
+        accel_z, set_accel_z: i16;
+    }
 }
 
 impl Default for Ds4ReportExData {
     /// Creates a new `Ds4ReportExData` with a valid default state (e.g., centered sticks).
     fn default() -> Self {
         let mut report: Self = unsafe { mem::zeroed() };
-        let base = Ds4Report::default();
-        report.thumb_lx = base.thumb_lx;
-        report.thumb_ly = base.thumb_ly;
-        report.thumb_rx = base.thumb_rx;
-        report.thumb_ry = base.thumb_ry;
-        report.buttons = base.buttons;
-        report.special = base.special;
-        report.trigger_l = base.trigger_l;
-        report.trigger_r = base.trigger_r;
+        report.apply_report(&Ds4Report::default());
         report
     }
 }
@@ -378,6 +1350,10 @@ impl Default for Ds4ReportExData {
 /// This is used for more advanced scenarios where you need to simulate more than basic inputs,
 /// such as gyroscope, accelerometer, or touchpad data. It is sent via the `update` method
 /// on a `TargetHandle<DualShock4>`.
+///
+/// The 63 bytes are the USB input report without its leading report ID. The driver adds
+/// the ID [`REPORT_ID`](Self::REPORT_ID) itself, so it can't be chosen. ViGEmBus only
+/// emulates a USB DualShock 4, which means Bluetooth reports (ID `0x11`) can't be sent.
 #[repr(C, packed)]
 pub union Ds4ReportEx {
     pub report: Ds4ReportExData,
@@ -417,8 +1393,216 @@ impl Default for Ds4ReportEx {
     }
 }
 
+impl Ds4ReportEx {
+    /// The HID report ID the driver puts in front of the report when passing it to the
+    /// host, which is that of a USB input report.
+    ///
+    /// It is not part of the 63-byte buffer. Code that compares the report with one read
+    /// from a real controller should prepend it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4ReportEx;
+    ///
+    /// let mut usb_report = vec![Ds4ReportEx::REPORT_ID];
+    /// usb_report.extend_from_slice(&Ds4ReportEx::default().to_bytes());
+    ///
+    /// assert_eq!(usb_report.len(), 64);
+    /// ```
+    pub const REPORT_ID: u8 = 0x01;
+
+    /// Creates an extended report from a standard [`Ds4Report`].
+    ///
+    /// The standard fields are copied over, while all extended fields keep
+    /// their default values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Report, Ds4ReportEx};
+    ///
+    /// let mut report = Ds4Report::default();
+    /// report.trigger_r = 255;
+    ///
+    /// let mut report_ex = Ds4ReportEx::from_report(&report);
+    /// report_ex.set_gyro(0.0, 90.0, 0.0);
+    ///
+    /// assert_eq!(report_ex.trigger_r, 255);
+    /// ```
+    pub fn from_report(report: &Ds4Report) -> Self {
+        let mut report_ex = Self::default();
+        report_ex.apply_report(report);
+        report_ex
+    }
+
+    /// Creates an extended report from its raw 63-byte buffer.
+    ///
+    /// Multi-byte fields are read in native byte order, which is little-endian on every
+    /// platform ViGEmBus runs on.
+    pub fn from_bytes(bytes: &[u8; 63]) -> Self {
+        Self {
+            report_buffer: *bytes,
+        }
+    }
+
+    /// Returns the raw 63-byte buffer of the extended report, see [`from_bytes`](Self::from_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::{Ds4Report, Ds4ReportEx};
+    ///
+    /// let mut report = Ds4Report::default();
+    /// report.trigger_r = 255;
+    ///
+    /// let bytes = Ds4ReportEx::from_report(&report).to_bytes();
+    /// let decoded = Ds4ReportEx::from_bytes(&bytes);
+    ///
+    /// assert_eq!(decoded.trigger_r, 255);
+    /// assert_eq!(decoded.to_bytes(), bytes);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 63] {
+        // Copy the data field instead of reading `report_buffer`, whose trailing bytes
+        // are uninitialized when the report was built through `report`.
+        let mut bytes = [0; 63];
+        // SAFETY: `Ds4ReportExData` is a packed struct of plain integers without padding,
+        // and the layout assertions at the end of this file check that it fits the buffer.
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &raw const self.report as *const u8,
+                bytes.as_mut_ptr(),
+                mem::size_of::<Ds4ReportExData>(),
+            );
+        }
+        bytes
+    }
+
+    /// Decodes an extended report from a byte slice in the format of [`to_bytes`](Self::to_bytes).
+    ///
+    /// Returns `None` unless the slice is exactly 63 bytes long.
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self::from_bytes)
+    }
+}
+
+/// Tracks the stateful touchpad protocol across consecutive extended reports.
+///
+/// The DS4 sends a short history of touch packets with every report: the newest one
+/// in `current_touch` and up to two older ones in `previous_touch`. Each packet also
+/// carries an incrementing counter. This helper takes care of all of that, so you only
+/// need to describe the contacts of each new frame.
+///
+/// Other inputs (sticks, buttons, motion, ...) can be set through [`report_mut`](Self::report_mut)
+/// and are carried over to every report produced by [`push_frame`](Self::push_frame).
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::ds4::{Ds4Touch, Ds4TouchSequencer};
+///
+/// let mut sequencer = Ds4TouchSequencer::new();
+///
+/// let mut touch = Ds4Touch::default();
+/// touch.set_touch_1(true, 1, 960, 471);
+/// touch.set_touch_2(false, 0, 0, 0);
+///
+/// let first = sequencer.push_frame(touch);
+/// let second = sequencer.push_frame(touch);
+///
+/// assert_eq!(first.touch_packets_n, 1);
+/// assert_eq!(second.touch_packets_n, 2);
+/// assert_eq!(
+///     second.current_touch.get_packet_counter(),
+///     first.current_touch.get_packet_counter().wrapping_add(1)
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct Ds4TouchSequencer {
+    report: Ds4ReportEx,
+    packet_counter: u8,
+}
+
+impl Ds4TouchSequencer {
+    /// Creates a new sequencer with an empty touch history and a default report.
+    pub fn new() -> Self {
+        Self::with_report(Ds4ReportEx::default())
+    }
+
+    /// Creates a new sequencer that builds on top of the given report.
+    ///
+    /// Any touch data already present in `report` is discarded.
+    pub fn with_report(report: Ds4ReportEx) -> Self {
+        let mut report = report;
+        report.touch_packets_n = 0;
+        report.current_touch = Self::released_touch();
+        report.previous_touch = [Self::released_touch(); 2];
+
+        Self {
+            report,
+            packet_counter: 0,
+        }
+    }
+
+    /// Returns a mutable reference to the report used as a base for every frame.
+    ///
+    /// Touch related fields are overwritten on every [`push_frame`](Self::push_frame).
+    #[inline]
+    pub fn report_mut(&mut self) -> &mut Ds4ReportExData {
+        &mut self.report
+    }
+
+    /// Pushes a new touch frame and returns the report to submit.
+    ///
+    /// The previous `current_touch` is shifted into the history, the packet counter is
+    /// stamped onto `touch`, and `touch_packets_n` is set to the number of consecutive
+    /// packets (newest first) that carry contact data. It drops back to `0` once every
+    /// packet in the history reports all fingers lifted.
+    pub fn push_frame(&mut self, touch: Ds4Touch) -> Ds4ReportEx {
+        let mut touch = touch;
+        touch.packet_counter = self.packet_counter;
+        self.packet_counter = self.packet_counter.wrapping_add(1);
+
+        let report = &mut self.report;
+        report.previous_touch = [report.current_touch, report.previous_touch[0]];
+        report.current_touch = touch;
+
+        let history = report.previous_touch;
+        let active_history = history.iter().take_while(|t| Self::is_active(t)).count();
+        report.touch_packets_n = if Self::is_active(&touch) || active_history > 0 {
+            1 + active_history as u8
+        } else {
+            0
+        };
+
+        self.report
+    }
+
+    /// Returns `true` if any finger is down in the given touch packet.
+    #[inline]
+    fn is_active(touch: &Ds4Touch) -> bool {
+        touch.get_is_down_1() || touch.get_is_down_2()
+    }
+
+    /// A touch packet with both fingers lifted.
+    #[inline]
+    fn released_touch() -> Ds4Touch {
+        let mut touch = Ds4Touch::default();
+        touch.set_touch_1(false, 0, 0, 0);
+        touch.set_touch_2(false, 0, 0, 0);
+        touch
+    }
+}
+
+impl Default for Ds4TouchSequencer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(not(windows), allow(dead_code))]
 pub(crate) struct Ds4SubmitReport {
     pub size: u32,
     pub serial_no: u32,
@@ -427,6 +1611,7 @@ pub(crate) struct Ds4SubmitReport {
 
 #[repr(C, packed)]
 #[derive(Clone, Copy, Default)]
+#[cfg_attr(not(windows), allow(dead_code))]
 pub(crate) struct Ds4SubmitReportEx {
     pub size: u32,
     pub serial_no: u32,
@@ -477,6 +1662,112 @@ impl Ds4LightbarColor {
             blue: b,
         }
     }
+
+    /// Creates a color from a `0xRRGGBB` value. The upper 8 bits are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4LightbarColor;
+    ///
+    /// let color = Ds4LightbarColor::from_hex(0xFF0080);
+    /// assert_eq!(color, Ds4LightbarColor::new(255, 0, 128));
+    /// assert_eq!(color.to_hex(), 0xFF0080);
+    /// ```
+    #[inline]
+    pub fn from_hex(hex: u32) -> Self {
+        let [_, r, g, b] = hex.to_be_bytes();
+        Self::new(r, g, b)
+    }
+
+    /// Returns the color as a `0xRRGGBB` value.
+    #[inline]
+    pub fn to_hex(&self) -> u32 {
+        u32::from_be_bytes([0, self.red, self.green, self.blue])
+    }
+
+    /// Creates a color from normalized `[r, g, b]` channels.
+    ///
+    /// Each channel is clamped to `0.0..=1.0` and scaled to `0..=255`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4LightbarColor;
+    ///
+    /// let color = Ds4LightbarColor::from_rgb_f32([1.0, 0.0, 0.5]);
+    /// assert_eq!(color, Ds4LightbarColor::new(255, 0, 128));
+    /// assert_eq!(Ds4LightbarColor::new(255, 0, 0).to_rgb_f32(), [1.0, 0.0, 0.0]);
+    /// ```
+    #[inline]
+    pub fn from_rgb_f32(rgb: [f32; 3]) -> Self {
+        let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::new(channel(rgb[0]), channel(rgb[1]), channel(rgb[2]))
+    }
+
+    /// Returns the color as normalized `[r, g, b]` channels in `0.0..=1.0`.
+    #[inline]
+    pub fn to_rgb_f32(&self) -> [f32; 3] {
+        [
+            self.red as f32 / 255.0,
+            self.green as f32 / 255.0,
+            self.blue as f32 / 255.0,
+        ]
+    }
+
+    /// Returns the color with every channel scaled by `factor`.
+    ///
+    /// Channels are rounded and clamped to `0..=255`, so factors above `1.0` saturate
+    /// and negative factors yield black.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4LightbarColor;
+    ///
+    /// let color = Ds4LightbarColor::new(200, 100, 0);
+    /// assert_eq!(color.with_brightness(0.5), Ds4LightbarColor::new(100, 50, 0));
+    /// assert_eq!(color.with_brightness(2.0), Ds4LightbarColor::new(255, 200, 0));
+    /// ```
+    #[inline]
+    pub fn with_brightness(&self, factor: f32) -> Self {
+        let channel = |value: u8| (value as f32 * factor).round().clamp(0.0, 255.0) as u8;
+        Self::new(channel(self.red), channel(self.green), channel(self.blue))
+    }
+
+    /// Converts the color for display on screen.
+    ///
+    /// The host sets the lightbar channels as linear LED intensities, while screens expect
+    /// sRGB-encoded values. This applies the sRGB transfer function, which brightens dim
+    /// channels to match how the physical lightbar is perceived.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::ds4::Ds4LightbarColor;
+    ///
+    /// let color = Ds4LightbarColor::new(255, 64, 0).to_gamma_corrected();
+    /// assert_eq!(color, Ds4LightbarColor::new(255, 137, 0));
+    /// ```
+    pub fn to_gamma_corrected(&self) -> Self {
+        let encode = |linear: f32| {
+            if linear <= 0.003_130_8 {
+                linear * 12.92
+            } else {
+                1.055 * linear.powf(1.0 / 2.4) - 0.055
+            }
+        };
+
+        let [r, g, b] = self.to_rgb_f32();
+        Self::from_rgb_f32([encode(r), encode(g), encode(b)])
+    }
+}
+
+impl From<[u8; 3]> for Ds4LightbarColor {
+    #[inline]
+    fn from([r, g, b]: [u8; 3]) -> Self {
+        Self::new(r, g, b)
+    }
 }
 
 /// A notification received from the bus for a DualShock 4 target.
@@ -517,13 +1808,141 @@ pub struct Ds4Notification {
     pub lightbar: Ds4LightbarColor,
 }
 
+impl Ds4Notification {
+    /// Returns the requested rumble motor strengths.
+    #[inline]
+    pub fn rumble(&self) -> Rumble {
+        Rumble {
+            large: self.large_motor,
+            small: self.small_motor,
+        }
+    }
+}
+
 /// A raw 64-byte output packet received from the bus for a DS4 target.
 ///
 /// This is for advanced use cases where you need to parse the raw output report from
 /// the bus yourself, which may contain more detailed information than the standard
 /// `Ds4Notification`. Obtain a receiver for this type via
 /// `TargetHandle<DualShock4>::register_notification_raw_buffer`.
+///
+/// The accessor methods decode the well-known fields of the USB output report
+/// (report ID `0x05`). Anything else can still be read from `buf` directly.
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::ds4::{Ds4LightbarColor, Ds4OutputBuffer};
+///
+/// let mut buf = [0u8; 64];
+/// buf[0] = 0x05;
+/// buf[4..11].copy_from_slice(&[64, 200, 255, 0, 128, 10, 20]);
+/// let output = Ds4OutputBuffer { buf };
+///
+/// assert_eq!(output.rumble().large, 200);
+/// assert_eq!(output.rumble().small, 64);
+/// assert_eq!(output.lightbar(), Ds4LightbarColor::new(255, 0, 128));
+/// assert_eq!(output.flash_on(), 10);
+/// assert_eq!(output.flash_off(), 20);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Ds4OutputBuffer {
     pub buf: [u8; 64],
 }
+
+impl Ds4OutputBuffer {
+    const REPORT_ID: usize = 0;
+    const SMALL_MOTOR: usize = 4;
+    const LARGE_MOTOR: usize = 5;
+    const LIGHTBAR_RED: usize = 6;
+    const LIGHTBAR_GREEN: usize = 7;
+    const LIGHTBAR_BLUE: usize = 8;
+    const FLASH_ON: usize = 9;
+    const FLASH_OFF: usize = 10;
+
+    /// Returns the HID report ID of the output report, `0x05` for USB output reports.
+    #[inline]
+    pub fn report_id(&self) -> u8 {
+        self.buf[Self::REPORT_ID]
+    }
+
+    /// Returns the requested rumble motor strengths.
+    #[inline]
+    pub fn rumble(&self) -> Rumble {
+        Rumble {
+            large: self.buf[Self::LARGE_MOTOR],
+            small: self.buf[Self::SMALL_MOTOR],
+        }
+    }
+
+    /// Returns the requested lightbar color.
+    #[inline]
+    pub fn lightbar(&self) -> Ds4LightbarColor {
+        Ds4LightbarColor::new(
+            self.buf[Self::LIGHTBAR_RED],
+            self.buf[Self::LIGHTBAR_GREEN],
+            self.buf[Self::LIGHTBAR_BLUE],
+        )
+    }
+
+    /// Returns the lightbar flash "on" duration, in units of 10 ms.
+    ///
+    /// A value of `0` together with a `flash_off` of `0` means the lightbar does not flash.
+    #[inline]
+    pub fn flash_on(&self) -> u8 {
+        self.buf[Self::FLASH_ON]
+    }
+
+    /// Returns the lightbar flash "off" duration, in units of 10 ms.
+    #[inline]
+    pub fn flash_off(&self) -> u8 {
+        self.buf[Self::FLASH_OFF]
+    }
+}
+
+/// A decoded DS4 output report, as produced by
+/// `TargetHandle<DualShock4>::register_notification_parsed`.
+///
+/// This carries more than a [`Ds4Notification`] (flash timings and the report ID)
+/// without requiring the raw byte offsets of a [`Ds4OutputBuffer`].
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::ds4::{Ds4FullOutput, Ds4OutputBuffer};
+///
+/// let mut buf = [0u8; 64];
+/// buf[0] = 0x05;
+/// buf[5] = 200; // Large motor
+/// buf[9] = 50; // Flash on for 500 ms
+/// let output = Ds4FullOutput::from(&Ds4OutputBuffer { buf });
+///
+/// assert_eq!(output.report_id, 0x05);
+/// assert_eq!(output.rumble.large, 200);
+/// assert_eq!(output.flash_on, 50);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ds4FullOutput {
+    /// The HID report ID, `0x05` for USB output reports.
+    pub report_id: u8,
+    /// The requested rumble motor strengths.
+    pub rumble: Rumble,
+    /// The requested lightbar color.
+    pub lightbar: Ds4LightbarColor,
+    /// The lightbar flash "on" duration, in units of 10 ms.
+    pub flash_on: u8,
+    /// The lightbar flash "off" duration, in units of 10 ms.
+    pub flash_off: u8,
+}
+
+impl From<&Ds4OutputBuffer> for Ds4FullOutput {
+    fn from(output: &Ds4OutputBuffer) -> Self {
+        Self {
+            report_id: output.report_id(),
+            rumble: output.rumble(),
+            lightbar: output.lightbar(),
+            flash_on: output.flash_on(),
+            flash_off: output.flash_off(),
+        }
+    }
+}
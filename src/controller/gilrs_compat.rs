@@ -0,0 +1,123 @@
+// Conversions from gilrs gamepads into virtual controller reports.
+//
+// `gilrs::ev::state::GamepadState` stores its data by platform-specific codes, so the
+// conversions take a `gilrs::Gamepad` instead, which knows how to map them to the
+// standard `Button` and `Axis` layout.
+
+use gilrs::{Axis, Button, Gamepad};
+
+#[cfg(feature = "ds4")]
+use crate::controller::ds4::{Ds4Button, Ds4Dpad, Ds4Report, Ds4SpecialButton};
+#[cfg(feature = "x360")]
+use crate::controller::x360::{X360Button, X360Report};
+
+/// Reads an analog trigger button as `0..=255`.
+fn trigger(gamepad: &Gamepad<'_>, button: Button) -> u8 {
+    match gamepad.button_data(button) {
+        Some(data) => (data.value().clamp(0.0, 1.0) * 255.0).round() as u8,
+        None => 0,
+    }
+}
+
+/// Maps the pressed gilrs buttons onto a flag set using `(gilrs button, flag)` pairs.
+fn collect_buttons<B: bitflags::Flags + Copy>(gamepad: &Gamepad<'_>, mapping: &[(Button, B)]) -> B {
+    let mut buttons = B::empty();
+    for (button, flag) in mapping {
+        if gamepad.is_pressed(*button) {
+            buttons.insert(*flag);
+        }
+    }
+    buttons
+}
+
+#[cfg(feature = "x360")]
+/// Mirrors a gilrs gamepad onto an [`X360Report`].
+///
+/// Buttons are mapped by position (`South` is `A`, `Mode` is `GUIDE`), `LeftTrigger2` and
+/// `RightTrigger2` become the analog triggers, and stick axes keep gilrs' "up is positive"
+/// orientation, which matches XInput.
+impl From<&Gamepad<'_>> for X360Report {
+    fn from(gamepad: &Gamepad<'_>) -> Self {
+        const MAPPING: &[(Button, X360Button)] = &[
+            (Button::South, X360Button::A),
+            (Button::East, X360Button::B),
+            (Button::West, X360Button::X),
+            (Button::North, X360Button::Y),
+            (Button::LeftTrigger, X360Button::LEFT_SHOULDER),
+            (Button::RightTrigger, X360Button::RIGHT_SHOULDER),
+            (Button::Select, X360Button::BACK),
+            (Button::Start, X360Button::START),
+            (Button::Mode, X360Button::GUIDE),
+            (Button::LeftThumb, X360Button::LEFT_THUMB),
+            (Button::RightThumb, X360Button::RIGHT_THUMB),
+            (Button::DPadUp, X360Button::DPAD_UP),
+            (Button::DPadDown, X360Button::DPAD_DOWN),
+            (Button::DPadLeft, X360Button::DPAD_LEFT),
+            (Button::DPadRight, X360Button::DPAD_RIGHT),
+        ];
+
+        let axis = |axis: Axis| (gamepad.value(axis).clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+
+        X360Report {
+            buttons: collect_buttons(gamepad, MAPPING),
+            left_trigger: trigger(gamepad, Button::LeftTrigger2),
+            right_trigger: trigger(gamepad, Button::RightTrigger2),
+            thumb_lx: axis(Axis::LeftStickX),
+            thumb_ly: axis(Axis::LeftStickY),
+            thumb_rx: axis(Axis::RightStickX),
+            thumb_ry: axis(Axis::RightStickY),
+        }
+    }
+}
+
+#[cfg(feature = "ds4")]
+/// Mirrors a gilrs gamepad onto a [`Ds4Report`].
+///
+/// Buttons are mapped by position (`South` is Cross, `Mode` is PS). `LeftTrigger2` and
+/// `RightTrigger2` set both the analog L2/R2 values and their digital bits. Stick axes
+/// are flipped vertically, since the DS4 reports "up" as `0`.
+impl From<&Gamepad<'_>> for Ds4Report {
+    fn from(gamepad: &Gamepad<'_>) -> Self {
+        const MAPPING: &[(Button, Ds4Button)] = &[
+            (Button::South, Ds4Button::CROSS),
+            (Button::East, Ds4Button::CIRCLE),
+            (Button::West, Ds4Button::SQUARE),
+            (Button::North, Ds4Button::TRIANGLE),
+            (Button::LeftTrigger, Ds4Button::SHOULDER_LEFT),
+            (Button::RightTrigger, Ds4Button::SHOULDER_RIGHT),
+            (Button::LeftTrigger2, Ds4Button::TRIGGER_LEFT),
+            (Button::RightTrigger2, Ds4Button::TRIGGER_RIGHT),
+            (Button::Select, Ds4Button::SHARE),
+            (Button::Start, Ds4Button::OPTIONS),
+            (Button::LeftThumb, Ds4Button::THUMB_LEFT),
+            (Button::RightThumb, Ds4Button::THUMB_RIGHT),
+        ];
+
+        let axis = |axis: Axis, sign: f32| {
+            let value = (gamepad.value(axis) * sign).clamp(-1.0, 1.0);
+            ((value + 1.0) * 127.5).round() as u8
+        };
+
+        let dpad_axis = |negative: Button, positive: Button| {
+            gamepad.is_pressed(positive) as i8 as f32 - gamepad.is_pressed(negative) as i8 as f32
+        };
+
+        let mut report = Ds4Report {
+            thumb_lx: axis(Axis::LeftStickX, 1.0),
+            thumb_ly: axis(Axis::LeftStickY, -1.0),
+            thumb_rx: axis(Axis::RightStickX, 1.0),
+            thumb_ry: axis(Axis::RightStickY, -1.0),
+            buttons: collect_buttons(gamepad, MAPPING).bits(),
+            trigger_l: trigger(gamepad, Button::LeftTrigger2),
+            trigger_r: trigger(gamepad, Button::RightTrigger2),
+            ..Default::default()
+        };
+
+        report.set_dpad(Ds4Dpad::from_vector(
+            dpad_axis(Button::DPadLeft, Button::DPadRight),
+            dpad_axis(Button::DPadDown, Button::DPadUp),
+        ));
+        report.set_special(Ds4SpecialButton::PS, gamepad.is_pressed(Button::Mode));
+        report
+    }
+}
@@ -1,4 +1,265 @@
+use std::fmt;
+
+use bitflags::Flags;
+use thiserror::Error;
+
+use crate::client::ClientError;
+use crate::target::{TargetHandle, TargetKind};
+
 #[cfg(feature = "ds4")]
 pub mod ds4;
+#[cfg(feature = "gilrs-compat")]
+mod gilrs_compat;
 #[cfg(feature = "x360")]
 pub mod x360;
+
+/// Rumble motor strengths requested by the host.
+///
+/// Both [`X360Notification`](x360::X360Notification) and
+/// [`Ds4Notification`](ds4::Ds4Notification) expose their rumble state as a `Rumble`,
+/// which allows writing force-feedback code that works for either controller type.
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::Rumble;
+///
+/// fn forward(rumble: Rumble) {
+///     println!("large={}, small={}", rumble.large, rumble.small);
+/// }
+///
+/// forward(Rumble { large: 255, small: 0 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rumble {
+    /// Rumble strength for the large (low-frequency) motor (0-255).
+    pub large: u8,
+    /// Rumble strength for the small (high-frequency) motor (0-255).
+    pub small: u8,
+}
+
+impl Rumble {
+    /// Returns the rumble with both motor strengths multiplied by `factor`.
+    ///
+    /// Results are rounded and saturate at `0` and `255`. A negative or NaN `factor`
+    /// turns both motors off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::Rumble;
+    ///
+    /// let rumble = Rumble { large: 200, small: 51 };
+    /// assert_eq!(rumble.scaled(0.5), Rumble { large: 100, small: 26 });
+    /// assert_eq!(rumble.scaled(2.0), Rumble { large: 255, small: 102 });
+    /// ```
+    pub fn scaled(&self, factor: f32) -> Rumble {
+        // Float to int `as` casts saturate and map NaN to zero.
+        let scale = |value: u8| (f32::from(value) * factor).round() as u8;
+        Rumble {
+            large: scale(self.large),
+            small: scale(self.small),
+        }
+    }
+
+    /// Returns a single intensity for devices with only one motor.
+    ///
+    /// This is the stronger of the two motors. Averaging them instead would halve an
+    /// effect that only drives one motor, which is how most games signal light rumble.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::controller::Rumble;
+    ///
+    /// assert_eq!(Rumble { large: 0, small: 180 }.combined(), 180);
+    /// assert_eq!(Rumble { large: 90, small: 40 }.combined(), 90);
+    /// ```
+    #[inline]
+    pub fn combined(&self) -> u8 {
+        self.large.max(self.small)
+    }
+}
+
+/// An eight-way D-Pad direction, shared by both controller types.
+///
+/// Xbox 360 reports store the D-Pad as four separate button flags, while DualShock 4
+/// reports store a single direction value. `Dpad8` can be applied to either through
+/// `X360Report::set_dpad` and, converted into a [`Ds4Dpad`](ds4::Ds4Dpad),
+/// `Ds4Report::set_dpad`.
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::{Dpad8, X360Button, X360Report};
+///
+/// let mut report = X360Report::default();
+/// report.set_dpad(Dpad8::NorthEast);
+///
+/// assert_eq!(report.buttons, X360Button::DPAD_UP | X360Button::DPAD_RIGHT);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dpad8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+    #[default]
+    Neutral,
+}
+
+// Only needed to map the direction onto the Xbox 360 D-Pad buttons.
+#[cfg(feature = "x360")]
+impl Dpad8 {
+    /// Returns the direction pointed to by the horizontal and vertical components, each
+    /// `-1`, `0` or `1`, with positive X pointing right and positive Y pointing up.
+    pub(crate) fn from_axes(x: i8, y: i8) -> Dpad8 {
+        match (x.signum(), y.signum()) {
+            (0, 1) => Dpad8::North,
+            (1, 1) => Dpad8::NorthEast,
+            (1, 0) => Dpad8::East,
+            (1, -1) => Dpad8::SouthEast,
+            (0, -1) => Dpad8::South,
+            (-1, -1) => Dpad8::SouthWest,
+            (-1, 0) => Dpad8::West,
+            (-1, 1) => Dpad8::NorthWest,
+            _ => Dpad8::Neutral,
+        }
+    }
+
+    /// Returns the horizontal and vertical components of the direction, see
+    /// [`from_axes`](Self::from_axes).
+    pub(crate) fn axes(self) -> (i8, i8) {
+        match self {
+            Dpad8::North => (0, 1),
+            Dpad8::NorthEast => (1, 1),
+            Dpad8::East => (1, 0),
+            Dpad8::SouthEast => (1, -1),
+            Dpad8::South => (0, -1),
+            Dpad8::SouthWest => (-1, -1),
+            Dpad8::West => (-1, 0),
+            Dpad8::NorthWest => (-1, 1),
+            Dpad8::Neutral => (0, 0),
+        }
+    }
+}
+
+/// The input report of a virtual controller, for code that doesn't care which controller
+/// type it drives.
+///
+/// Implemented by [`X360Report`](x360::X360Report) and [`Ds4Report`](ds4::Ds4Report).
+/// Sticks use the Xbox convention on both: `-1.0..=1.0` with positive Y pointing up.
+/// Face buttons are named after their Xbox position, so `A` is `CROSS` on a DS4.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vigem_rust::client::ClientError;
+/// use vigem_rust::controller::ControllerReport;
+/// use vigem_rust::{Client, TargetHandle, X360Report};
+///
+/// fn jump<R: ControllerReport>(handle: &TargetHandle<R::Kind>) -> Result<(), ClientError> {
+///     let mut report = R::default();
+///     report.set_button_a(true);
+///     report.set_left_stick_normalized(0.0, 1.0);
+///     report.submit(handle)
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::connect()?;
+/// let x360 = client.new_x360_target().plugin()?;
+/// x360.wait_for_ready()?;
+/// jump::<X360Report>(&x360)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ControllerReport: Default + Copy {
+    /// The controller type this report is submitted to.
+    type Kind: TargetKind<Report = Self>;
+
+    /// Sets the left thumbstick from `-1.0..=1.0` on both axes. Values are clamped.
+    fn set_left_stick_normalized(&mut self, x: f32, y: f32);
+
+    /// Sets the right thumbstick from `-1.0..=1.0` on both axes. Values are clamped.
+    fn set_right_stick_normalized(&mut self, x: f32, y: f32);
+
+    /// Sets both triggers from `0.0..=1.0`. Values are clamped.
+    fn set_triggers_normalized(&mut self, left: f32, right: f32);
+
+    /// Sets the bottom face button (`A` on Xbox, `CROSS` on DS4).
+    fn set_button_a(&mut self, pressed: bool);
+
+    /// Sets the right face button (`B` on Xbox, `CIRCLE` on DS4).
+    fn set_button_b(&mut self, pressed: bool);
+
+    /// Sets the left face button (`X` on Xbox, `SQUARE` on DS4).
+    fn set_button_x(&mut self, pressed: bool);
+
+    /// Sets the top face button (`Y` on Xbox, `TRIANGLE` on DS4).
+    fn set_button_y(&mut self, pressed: bool);
+
+    /// Submits the report through `handle`, see `TargetHandle::update`.
+    fn submit(&self, handle: &TargetHandle<Self::Kind>) -> Result<(), ClientError>;
+}
+
+/// Maps `0.0..=1.0` to a trigger value (0-255). NaN maps to `0`.
+pub(crate) fn normalized_trigger(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// The error returned when parsing a button set from a string fails.
+///
+/// Returned by the `FromStr` implementations of [`X360Button`](x360::X360Button) and
+/// [`Ds4Button`](ds4::Ds4Button).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown button name: `{0}`")]
+pub struct ParseButtonError(String);
+
+/// Parses a comma-separated list of button names (case-insensitive) into a flag set.
+///
+/// Hexadecimal values such as `0x0800` are accepted for bits without a name, so the
+/// output of [`fmt_buttons`] always parses back into the same value.
+pub(crate) fn parse_buttons<B: Flags<Bits = u16>>(s: &str) -> Result<B, ParseButtonError> {
+    let mut buttons = B::empty();
+
+    for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let button = match name.strip_prefix("0x").or_else(|| name.strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16).ok().map(B::from_bits_retain),
+            None => B::from_name(&name.to_ascii_uppercase()),
+        };
+        buttons.insert(button.ok_or_else(|| ParseButtonError(name.to_string()))?);
+    }
+
+    Ok(buttons)
+}
+
+/// Writes the names of the set buttons separated by `", "`, with unnamed bits as hex.
+pub(crate) fn fmt_buttons<B: Flags<Bits = u16>>(
+    buttons: &B,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let mut first = true;
+    let mut separator = |f: &mut fmt::Formatter<'_>| {
+        if !std::mem::take(&mut first) {
+            f.write_str(", ")?;
+        }
+        Ok(())
+    };
+
+    for (name, _) in buttons.iter_names() {
+        separator(f)?;
+        f.write_str(name)?;
+    }
+
+    let unnamed = buttons.bits() & !B::from_bits_truncate(buttons.bits()).bits();
+    if unnamed != 0 {
+        separator(f)?;
+        write!(f, "{unnamed:#06x}")?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,13 @@
 use bitflags::bitflags;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::client::ClientError;
+use crate::controller::{
+    ControllerReport, Dpad8, ParseButtonError, Rumble, fmt_buttons, normalized_trigger,
+    parse_buttons,
+};
+use crate::target::{TargetHandle, Xbox360};
 
 bitflags! {
     /// Represents the digital buttons on a virtual Xbox 360 controller.
@@ -31,6 +40,121 @@ bitflags! {
     }
 }
 
+impl X360Button {
+    /// Button combinations that hosts commonly reserve for system actions.
+    ///
+    /// - `GUIDE + BACK` and `GUIDE + START` are used by launchers such as Steam Big
+    ///   Picture and by the Xbox Game Bar for overlay shortcuts.
+    /// - `BACK + START` is the default "exit" hotkey in many emulator frontends.
+    pub const RESERVED_COMBOS: &'static [X360Button] = &[
+        X360Button::GUIDE.union(X360Button::BACK),
+        X360Button::GUIDE.union(X360Button::START),
+        X360Button::BACK.union(X360Button::START),
+    ];
+
+    /// Returns `true` if the pressed buttons include one of the [`RESERVED_COMBOS`](Self::RESERVED_COMBOS).
+    ///
+    /// Macro tools can use this to warn users before sending a combination the host
+    /// may interpret specially.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Button;
+    ///
+    /// assert!((X360Button::GUIDE | X360Button::BACK).is_reserved_combo());
+    /// assert!((X360Button::BACK | X360Button::START | X360Button::A).is_reserved_combo());
+    /// assert!(!(X360Button::GUIDE | X360Button::A).is_reserved_combo());
+    /// assert!(!X360Button::START.is_reserved_combo());
+    /// ```
+    #[inline]
+    pub fn is_reserved_combo(&self) -> bool {
+        Self::RESERVED_COMBOS
+            .iter()
+            .any(|combo| self.contains(*combo))
+    }
+
+    /// Combines a slice of buttons into a single set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Button;
+    ///
+    /// let buttons = X360Button::from_slice(&[X360Button::A, X360Button::DPAD_UP]);
+    /// assert_eq!(buttons, X360Button::A | X360Button::DPAD_UP);
+    /// assert!(X360Button::from_slice(&[]).is_empty());
+    /// ```
+    #[inline]
+    pub fn from_slice(buttons: &[X360Button]) -> Self {
+        buttons.iter().copied().collect()
+    }
+
+    /// Returns the names of the pressed buttons, in flag definition order.
+    ///
+    /// Bits without a named flag are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Button;
+    ///
+    /// let buttons = X360Button::A | X360Button::DPAD_UP;
+    /// assert_eq!(buttons.names(), ["DPAD_UP", "A"]);
+    /// ```
+    pub fn names(&self) -> Vec<&'static str> {
+        self.iter_names().map(|(name, _)| name).collect()
+    }
+
+    /// Returns an iterator over the individual pressed buttons, in flag definition order.
+    ///
+    /// Each item is a single named flag, e.g. for rendering a list of pressed buttons.
+    /// Bits without a named flag are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Button;
+    ///
+    /// let buttons = X360Button::A | X360Button::DPAD_UP;
+    /// let pressed: Vec<_> = buttons.pressed_iter().collect();
+    /// assert_eq!(pressed, [X360Button::DPAD_UP, X360Button::A]);
+    /// ```
+    pub fn pressed_iter(&self) -> impl Iterator<Item = X360Button> + use<> {
+        self.iter_names().map(|(_, button)| button)
+    }
+}
+
+impl FromStr for X360Button {
+    type Err = ParseButtonError;
+
+    /// Parses a comma-separated list of button names, e.g. "A, left_shoulder".
+    ///
+    /// Names match the flag constants and are case-insensitive. Bits without a name
+    /// can be given in hexadecimal (`0x0800`). An empty string yields no buttons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Button;
+    ///
+    /// let buttons: X360Button = "A, left_shoulder".parse().unwrap();
+    /// assert_eq!(buttons, X360Button::A | X360Button::LEFT_SHOULDER);
+    /// assert_eq!(buttons.to_string(), "LEFT_SHOULDER, A");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_buttons(s)
+    }
+}
+
+impl fmt::Display for X360Button {
+    /// Formats the pressed buttons as a comma-separated list of names, which
+    /// [`FromStr`] accepts again.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_buttons(self, f)
+    }
+}
+
 /// Represents the full input state of a virtual Xbox 360 controller.
 ///
 /// An instance of this struct is sent to the bus via `TargetHandle::update` to
@@ -75,8 +199,448 @@ pub struct X360Report {
     pub thumb_ry: i16,
 }
 
+impl X360Report {
+    /// Creates a builder to construct a report with chained calls.
+    ///
+    /// The builder starts from [`X360Report::default()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{X360Button, X360Report};
+    ///
+    /// let report = X360Report::builder()
+    ///     .buttons(X360Button::A)
+    ///     .right_trigger(255)
+    ///     .build();
+    ///
+    /// assert_eq!(report.buttons, X360Button::A);
+    /// assert_eq!(report.right_trigger, 255);
+    /// ```
+    #[inline]
+    pub fn builder() -> X360ReportBuilder {
+        X360ReportBuilder::default()
+    }
+
+    /// Returns a report with no buttons pressed, both triggers released and both sticks
+    /// centered.
+    ///
+    /// This is the same as [`X360Report::default()`], but spells out the intent when
+    /// returning the controller to rest, e.g. `*report = X360Report::neutral()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Report;
+    ///
+    /// let report = X360Report::neutral();
+    /// assert!(report.buttons.is_empty());
+    /// assert_eq!((report.left_trigger, report.thumb_lx), (0, 0));
+    /// ```
+    #[inline]
+    pub fn neutral() -> Self {
+        Self::default()
+    }
+
+    /// Releases all buttons and triggers and centers both sticks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{X360Button, X360Report};
+    ///
+    /// let mut report = X360Report::builder()
+    ///     .buttons(X360Button::A | X360Button::START)
+    ///     .right_trigger(255)
+    ///     .left_stick(16384, -16384)
+    ///     .build();
+    /// report.all_released();
+    ///
+    /// assert_eq!(report.to_bytes(), X360Report::neutral().to_bytes());
+    /// ```
+    #[inline]
+    pub fn all_released(&mut self) {
+        *self = Self::neutral();
+    }
+
+    /// Sets the D-Pad to an eight-way direction.
+    ///
+    /// Diagonals press the two neighbouring D-Pad buttons, and [`Dpad8::Neutral`]
+    /// releases all four. The other buttons are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{Dpad8, X360Button, X360Report};
+    ///
+    /// let mut report = X360Report::default();
+    /// report.buttons = X360Button::A | X360Button::DPAD_UP;
+    /// report.set_dpad(Dpad8::SouthWest);
+    ///
+    /// assert_eq!(
+    ///     report.buttons,
+    ///     X360Button::A | X360Button::DPAD_DOWN | X360Button::DPAD_LEFT
+    /// );
+    /// ```
+    pub fn set_dpad(&mut self, dpad: Dpad8) {
+        let (x, y) = dpad.axes();
+        self.buttons.set(X360Button::DPAD_UP, y > 0);
+        self.buttons.set(X360Button::DPAD_DOWN, y < 0);
+        self.buttons.set(X360Button::DPAD_LEFT, x < 0);
+        self.buttons.set(X360Button::DPAD_RIGHT, x > 0);
+    }
+
+    /// Returns the eight-way direction of the pressed D-Pad buttons.
+    ///
+    /// This is the inverse of [`set_dpad`](Self::set_dpad). Opposite buttons pressed at
+    /// the same time cancel each other out, like on a physical D-Pad.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{Dpad8, X360Button, X360Report};
+    ///
+    /// let mut report = X360Report::default();
+    /// report.buttons = X360Button::DPAD_UP | X360Button::DPAD_LEFT;
+    /// assert_eq!(report.dpad(), Dpad8::NorthWest);
+    ///
+    /// report.buttons.insert(X360Button::DPAD_DOWN);
+    /// assert_eq!(report.dpad(), Dpad8::West);
+    /// ```
+    pub fn dpad(&self) -> Dpad8 {
+        let axis = |positive: X360Button, negative: X360Button| {
+            i8::from(self.buttons.contains(positive)) - i8::from(self.buttons.contains(negative))
+        };
+        Dpad8::from_axes(
+            axis(X360Button::DPAD_RIGHT, X360Button::DPAD_LEFT),
+            axis(X360Button::DPAD_UP, X360Button::DPAD_DOWN),
+        )
+    }
+
+    /// Decodes a report from its raw 12-byte XUSB wire format.
+    ///
+    /// The layout is the little-endian `XUSB_REPORT` structure: the button mask
+    /// (2 bytes), both triggers (1 byte each), then the four thumbstick axes
+    /// (2 bytes each). Button bits without a named [`X360Button`] flag are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{X360Button, X360Report};
+    ///
+    /// let report = X360Report::from_bytes(&[0x00, 0x10, 0, 255, 0, 0x40, 0, 0, 0, 0, 0, 0]);
+    ///
+    /// assert_eq!(report.buttons, X360Button::A);
+    /// assert_eq!(report.right_trigger, 255);
+    /// assert_eq!(report.thumb_lx, 16384);
+    /// ```
+    pub fn from_bytes(bytes: &[u8; 12]) -> Self {
+        let axis = |i: usize| i16::from_le_bytes([bytes[i], bytes[i + 1]]);
+
+        Self {
+            buttons: X360Button::from_bits_retain(u16::from_le_bytes([bytes[0], bytes[1]])),
+            left_trigger: bytes[2],
+            right_trigger: bytes[3],
+            thumb_lx: axis(4),
+            thumb_ly: axis(6),
+            thumb_rx: axis(8),
+            thumb_ry: axis(10),
+        }
+    }
+
+    /// Encodes the report into its raw 12-byte XUSB wire format.
+    ///
+    /// This is the inverse of [`from_bytes`](Self::from_bytes). Multi-byte values are
+    /// always written as little-endian, so the bytes can be sent to another machine,
+    /// e.g. to forward controller state over the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{X360Button, X360Report};
+    ///
+    /// let report = X360Report::builder()
+    ///     .buttons(X360Button::A)
+    ///     .left_stick(16384, -1)
+    ///     .build();
+    /// let bytes = report.to_bytes();
+    ///
+    /// assert_eq!(bytes[..8], [0x00, 0x10, 0, 0, 0x00, 0x40, 0xFF, 0xFF]);
+    /// assert_eq!(X360Report::from_bytes(&bytes).thumb_lx, 16384);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+        bytes[0..2].copy_from_slice(&self.buttons.bits().to_le_bytes());
+        bytes[2] = self.left_trigger;
+        bytes[3] = self.right_trigger;
+        bytes[4..6].copy_from_slice(&self.thumb_lx.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.thumb_ly.to_le_bytes());
+        bytes[8..10].copy_from_slice(&self.thumb_rx.to_le_bytes());
+        bytes[10..12].copy_from_slice(&self.thumb_ry.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a report from a byte slice in the format of [`to_bytes`](Self::to_bytes).
+    ///
+    /// Returns `None` unless the slice is exactly 12 bytes long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Report;
+    ///
+    /// let bytes = X360Report::default().to_bytes();
+    /// assert!(X360Report::from_slice(&bytes).is_some());
+    /// assert!(X360Report::from_slice(&bytes[..11]).is_none());
+    /// ```
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        bytes.try_into().ok().map(Self::from_bytes)
+    }
+
+    /// Applies a deadzone to the thumbsticks and triggers.
+    ///
+    /// A thumbstick whose distance from the center is below `stick_dz` is centered, so
+    /// diagonal drift is filtered the same as drift along a single axis. Triggers below
+    /// `trigger_dz` are released. Values outside the deadzones are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Report;
+    ///
+    /// let mut report = X360Report::builder()
+    ///     .left_stick(1000, -1200)
+    ///     .right_stick(20000, 0)
+    ///     .left_trigger(10)
+    ///     .right_trigger(200)
+    ///     .build();
+    /// report.apply_deadzone(7849, 30);
+    ///
+    /// assert_eq!((report.thumb_lx, report.thumb_ly), (0, 0));
+    /// assert_eq!((report.thumb_rx, report.thumb_ry), (20000, 0));
+    /// assert_eq!((report.left_trigger, report.right_trigger), (0, 200));
+    /// ```
+    pub fn apply_deadzone(&mut self, stick_dz: i16, trigger_dz: u8) {
+        let stick_dz = i64::from(stick_dz.unsigned_abs());
+        let in_deadzone = |x: i16, y: i16| {
+            let (x, y) = (i64::from(x), i64::from(y));
+            x * x + y * y < stick_dz * stick_dz
+        };
+
+        if in_deadzone(self.thumb_lx, self.thumb_ly) {
+            self.thumb_lx = 0;
+            self.thumb_ly = 0;
+        }
+        if in_deadzone(self.thumb_rx, self.thumb_ry) {
+            self.thumb_rx = 0;
+            self.thumb_ry = 0;
+        }
+
+        if self.left_trigger < trigger_dz {
+            self.left_trigger = 0;
+        }
+        if self.right_trigger < trigger_dz {
+            self.right_trigger = 0;
+        }
+    }
+
+    /// Sets `thumb_lx` from a wider integer, clamping it to the `i16` range.
+    ///
+    /// A plain `as i16` cast wraps out-of-range values around, so a stick computed
+    /// slightly past full deflection would snap to the opposite extreme instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Report;
+    ///
+    /// let mut report = X360Report::default();
+    /// report.set_thumb_lx_clamped(40_000);
+    /// assert_eq!(report.thumb_lx, i16::MAX);
+    ///
+    /// report.set_thumb_ly_clamped(-40_000);
+    /// assert_eq!(report.thumb_ly, i16::MIN);
+    /// ```
+    #[inline]
+    pub fn set_thumb_lx_clamped(&mut self, value: i32) {
+        self.thumb_lx = clamp_axis(value);
+    }
+
+    /// Sets `thumb_ly`, clamping it to the `i16` range. See [`set_thumb_lx_clamped`](Self::set_thumb_lx_clamped).
+    #[inline]
+    pub fn set_thumb_ly_clamped(&mut self, value: i32) {
+        self.thumb_ly = clamp_axis(value);
+    }
+
+    /// Sets `thumb_rx`, clamping it to the `i16` range. See [`set_thumb_lx_clamped`](Self::set_thumb_lx_clamped).
+    #[inline]
+    pub fn set_thumb_rx_clamped(&mut self, value: i32) {
+        self.thumb_rx = clamp_axis(value);
+    }
+
+    /// Sets `thumb_ry`, clamping it to the `i16` range. See [`set_thumb_lx_clamped`](Self::set_thumb_lx_clamped).
+    #[inline]
+    pub fn set_thumb_ry_clamped(&mut self, value: i32) {
+        self.thumb_ry = clamp_axis(value);
+    }
+
+    /// Linearly interpolates between this report and `other`.
+    ///
+    /// Sticks and triggers move from `self` at `t = 0.0` to `other` at `t = 1.0`,
+    /// rounded to the nearest value. Buttons can't be blended, so `other`'s buttons are
+    /// used once `t` reaches `0.5`. `t` is clamped to `0.0..=1.0`, and NaN counts as `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::{X360Button, X360Report};
+    ///
+    /// let from = X360Report::default();
+    /// let to = X360Report::builder()
+    ///     .buttons(X360Button::A)
+    ///     .left_stick(1000, -1000)
+    ///     .right_trigger(255)
+    ///     .build();
+    ///
+    /// let start = from.lerp(&to, 0.0);
+    /// assert_eq!((start.thumb_lx, start.right_trigger), (0, 0));
+    /// assert!(start.buttons.is_empty());
+    ///
+    /// let before_half = from.lerp(&to, 0.49);
+    /// assert!(before_half.buttons.is_empty());
+    ///
+    /// let half = from.lerp(&to, 0.5);
+    /// assert_eq!((half.thumb_lx, half.thumb_ly), (500, -500));
+    /// assert_eq!(half.right_trigger, 128);
+    /// assert_eq!(half.buttons, X360Button::A);
+    ///
+    /// let end = from.lerp(&to, 1.0);
+    /// assert_eq!((end.thumb_lx, end.thumb_ly, end.right_trigger), (1000, -1000, 255));
+    /// ```
+    pub fn lerp(&self, other: &X360Report, t: f32) -> X360Report {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let axis =
+            |a: i16, b: i16| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as i16;
+        let trigger =
+            |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+
+        X360Report {
+            buttons: if t < 0.5 { self.buttons } else { other.buttons },
+            left_trigger: trigger(self.left_trigger, other.left_trigger),
+            right_trigger: trigger(self.right_trigger, other.right_trigger),
+            thumb_lx: axis(self.thumb_lx, other.thumb_lx),
+            thumb_ly: axis(self.thumb_ly, other.thumb_ly),
+            thumb_rx: axis(self.thumb_rx, other.thumb_rx),
+            thumb_ry: axis(self.thumb_ry, other.thumb_ry),
+        }
+    }
+}
+
+/// Clamps a wide value into the thumbstick axis range instead of wrapping it.
+fn clamp_axis(value: i32) -> i16 {
+    value.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+}
+
+/// Maps `-1.0..=1.0` to a thumbstick axis. NaN maps to the center.
+fn normalized_axis(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * f32::from(i16::MAX)).round() as i16
+}
+
+impl ControllerReport for X360Report {
+    type Kind = Xbox360;
+
+    fn set_left_stick_normalized(&mut self, x: f32, y: f32) {
+        self.thumb_lx = normalized_axis(x);
+        self.thumb_ly = normalized_axis(y);
+    }
+
+    fn set_right_stick_normalized(&mut self, x: f32, y: f32) {
+        self.thumb_rx = normalized_axis(x);
+        self.thumb_ry = normalized_axis(y);
+    }
+
+    fn set_triggers_normalized(&mut self, left: f32, right: f32) {
+        self.left_trigger = normalized_trigger(left);
+        self.right_trigger = normalized_trigger(right);
+    }
+
+    fn set_button_a(&mut self, pressed: bool) {
+        self.buttons.set(X360Button::A, pressed);
+    }
+
+    fn set_button_b(&mut self, pressed: bool) {
+        self.buttons.set(X360Button::B, pressed);
+    }
+
+    fn set_button_x(&mut self, pressed: bool) {
+        self.buttons.set(X360Button::X, pressed);
+    }
+
+    fn set_button_y(&mut self, pressed: bool) {
+        self.buttons.set(X360Button::Y, pressed);
+    }
+
+    fn submit(&self, handle: &TargetHandle<Xbox360>) -> Result<(), ClientError> {
+        handle.update(self)
+    }
+}
+
+/// A builder for [`X360Report`].
+///
+/// Obtain one from [`X360Report::builder()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct X360ReportBuilder {
+    report: X360Report,
+}
+
+impl X360ReportBuilder {
+    /// Sets the pressed buttons, replacing any previously set buttons.
+    #[inline]
+    pub fn buttons(mut self, buttons: X360Button) -> Self {
+        self.report.buttons = buttons;
+        self
+    }
+
+    /// Sets the left trigger value (0-255).
+    #[inline]
+    pub fn left_trigger(mut self, value: u8) -> Self {
+        self.report.left_trigger = value;
+        self
+    }
+
+    /// Sets the right trigger value (0-255).
+    #[inline]
+    pub fn right_trigger(mut self, value: u8) -> Self {
+        self.report.right_trigger = value;
+        self
+    }
+
+    /// Sets the left thumbstick position. 0 is center on both axes.
+    #[inline]
+    pub fn left_stick(mut self, x: i16, y: i16) -> Self {
+        self.report.thumb_lx = x;
+        self.report.thumb_ly = y;
+        self
+    }
+
+    /// Sets the right thumbstick position. 0 is center on both axes.
+    #[inline]
+    pub fn right_stick(mut self, x: i16, y: i16) -> Self {
+        self.report.thumb_rx = x;
+        self.report.thumb_ry = y;
+        self
+    }
+
+    /// Returns the constructed report.
+    #[inline]
+    pub fn build(self) -> X360Report {
+        self.report
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(not(windows), allow(dead_code))]
 pub(crate) struct XusbSubmitReport {
     pub size: u32,
     pub serial_no: u32,
@@ -89,6 +653,10 @@ pub(crate) struct XusbSubmitReport {
 /// or the player index assigned to the controller. You can receive these by
 /// calling `TargetHandle::<Xbox360>::register_notification`.
 ///
+/// The fields of this struct are everything the ViGEmBus driver forwards for Xbox 360
+/// targets. Other host output, such as headset audio or guide button related reports,
+/// is not exposed by the driver and therefore can't be received.
+///
 /// # Examples
 /// ```no_run
 /// # use vigem_rust::{Client, target::Xbox360};
@@ -116,3 +684,116 @@ pub struct X360Notification {
     /// This is the most reliable way to determine the controller's player index.
     pub led_number: u8,
 }
+
+impl X360Notification {
+    /// Returns the requested rumble motor strengths.
+    #[inline]
+    pub fn rumble(&self) -> Rumble {
+        Rumble {
+            large: self.large_motor,
+            small: self.small_motor,
+        }
+    }
+
+    /// Returns [`led_number`](Self::led_number) as a zero-based player index (0-3).
+    ///
+    /// ViGEmBus already decodes the XUSB LED pattern into the player number before it
+    /// sends the notification. Values outside 0-3 don't identify a player and yield
+    /// `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vigem_rust::X360Notification;
+    ///
+    /// let mut notification = X360Notification {
+    ///     large_motor: 0,
+    ///     small_motor: 0,
+    ///     led_number: 1,
+    /// };
+    /// assert_eq!(notification.player_index(), Some(1));
+    ///
+    /// notification.led_number = 4;
+    /// assert_eq!(notification.player_index(), None);
+    /// ```
+    #[inline]
+    pub fn player_index(&self) -> Option<u8> {
+        match self.led_number {
+            n @ 0..=3 => Some(n),
+            _ => None,
+        }
+    }
+}
+
+/// The raw response buffer of an Xbox 360 notification request, as filled in by the bus.
+///
+/// This is for advanced use cases that want to decode the driver's response themselves,
+/// e.g. to pick up fields added by a future driver release before this crate knows about
+/// them. Obtain a receiver for this type via
+/// `TargetHandle<Xbox360>::register_notification_raw_buffer`.
+///
+/// The buffer mirrors `XUSB_REQUEST_NOTIFICATION`: the request size and serial number
+/// as little-endian `u32`s, followed by the large motor, small motor and LED bytes. The
+/// accessor methods decode the latter; everything else can be read from `buf` directly.
+///
+/// # Examples
+///
+/// ```
+/// use vigem_rust::controller::x360::X360NotificationBuffer;
+///
+/// let mut buf = [0u8; 12];
+/// buf[8..11].copy_from_slice(&[200, 64, 1]);
+/// let raw = X360NotificationBuffer { buf };
+///
+/// assert_eq!(raw.large_motor(), 200);
+/// assert_eq!(raw.small_motor(), 64);
+/// assert_eq!(raw.notification().player_index(), Some(1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X360NotificationBuffer {
+    pub buf: [u8; 12],
+}
+
+impl X360NotificationBuffer {
+    const SERIAL_NO: usize = 4;
+    const LARGE_MOTOR: usize = 8;
+    const SMALL_MOTOR: usize = 9;
+    const LED_NUMBER: usize = 10;
+
+    /// Returns the serial number of the target the response belongs to.
+    #[inline]
+    pub fn serial_no(&self) -> u32 {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&self.buf[Self::SERIAL_NO..Self::SERIAL_NO + 4]);
+        u32::from_le_bytes(bytes)
+    }
+
+    /// Returns the requested strength of the large motor.
+    #[inline]
+    pub fn large_motor(&self) -> u8 {
+        self.buf[Self::LARGE_MOTOR]
+    }
+
+    /// Returns the requested strength of the small motor.
+    #[inline]
+    pub fn small_motor(&self) -> u8 {
+        self.buf[Self::SMALL_MOTOR]
+    }
+
+    /// Returns the player number indicated by the LED, see [`X360Notification::led_number`].
+    #[inline]
+    pub fn led_number(&self) -> u8 {
+        self.buf[Self::LED_NUMBER]
+    }
+
+    /// Decodes the buffer into the [`X360Notification`] that `register_notification`
+    /// would have produced.
+    #[inline]
+    pub fn notification(&self) -> X360Notification {
+        X360Notification {
+            large_motor: self.large_motor(),
+            small_motor: self.small_motor(),
+            led_number: self.led_number(),
+        }
+    }
+}
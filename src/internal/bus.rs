@@ -1,37 +1,28 @@
+use std::any::Any;
 use std::ffi::c_void;
-use std::ptr;
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, mpsc};
 
 use thiserror::Error;
-use windows::Win32::Devices::DeviceAndDriverInstallation::{
-    DIGCF_DEVICEINTERFACE, DIGCF_PRESENT, HDEVINFO, SP_DEVICE_INTERFACE_DATA,
-    SP_DEVICE_INTERFACE_DETAIL_DATA_W, SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInterfaces,
-    SetupDiGetClassDevsW, SetupDiGetDeviceInterfaceDetailW,
-};
-use windows::Win32::Foundation::{
-    CloseHandle, ERROR_NO_MORE_ITEMS, GENERIC_READ, GENERIC_WRITE, HANDLE,
-};
-use windows::Win32::Storage::FileSystem::{
-    CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_FLAG_NO_BUFFERING, FILE_FLAG_OVERLAPPED,
-    FILE_FLAG_WRITE_THROUGH, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
-};
-use windows::Win32::System::IO::DeviceIoControl;
-use windows::core::{GUID, PCWSTR};
 
+use crate::client::{IoFlags, MetricsCounters, ThreadPriority};
 #[cfg(feature = "ds4")]
 use crate::controller::ds4::{
-    Ds4Notification, Ds4OutputBuffer, Ds4Report, Ds4ReportEx, Ds4SubmitReport, Ds4SubmitReportEx,
+    Ds4FullOutput, Ds4Notification, Ds4OutputBuffer, Ds4Report, Ds4ReportEx,
 };
 #[cfg(feature = "x360")]
-use crate::controller::x360::{X360Notification, X360Report, XusbSubmitReport};
-use crate::internal::ioctl::*;
+use crate::controller::x360::{X360Notification, X360NotificationBuffer, X360Report};
+#[cfg(windows)]
+use crate::internal::driver::{self, DriverBus};
+use crate::internal::logging::{debug, trace};
 use crate::internal::notification_workers::*;
-use crate::internal::overlapped::OverlappedCall;
-use crate::target::Target;
+#[cfg(feature = "mock")]
+use crate::mock::MockBus;
+use crate::target::{Target, TargetType};
 
 #[derive(Debug, Error)]
 pub enum BusError {
+    #[cfg(windows)]
     #[error("Windows API Error: {0}")]
     WindowsAPIError(#[from] windows::core::Error),
 
@@ -40,272 +31,238 @@ pub enum BusError {
 
     #[error("Bus not found")]
     BusNotFound,
-}
 
-const VIGEM_GUID: GUID = GUID::from_values(
-    0x96E42B22,
-    0xF5E9,
-    0x42F8,
-    [0xB0, 0x43, 0xED, 0x0F, 0x93, 0x2F, 0x01, 0x4F],
-);
+    #[error("Failed to spawn notification thread: {0}")]
+    ThreadSpawnFailed(#[from] std::io::Error),
 
-struct BusInner {
-    handle: HANDLE,
+    #[error("Notification thread stopped unexpectedly")]
+    NotificationThreadStopped,
+
+    #[error("Serial number {0} is already in use")]
+    SerialInUse(u32),
+
+    #[error("No target with serial number {0} is plugged in")]
+    TargetNotFound(u32),
 }
 
-impl Drop for BusInner {
-    fn drop(&mut self) {
-        unsafe {
-            let _ = CloseHandle(self.handle);
+impl BusError {
+    /// Returns `true` if the bus rejected a plugin request because the
+    /// requested serial number is already in use.
+    pub(crate) fn is_slot_in_use(&self) -> bool {
+        match self {
+            BusError::SerialInUse(_) => true,
+            #[cfg(windows)]
+            BusError::WindowsAPIError(e) => driver::is_slot_in_use_windows_error(e),
+            _ => false,
         }
     }
+
+    /// Returns `true` if the error reflects a temporary condition on the driver
+    /// side, so repeating the same operation later may succeed.
+    pub(crate) fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(windows)]
+            BusError::WindowsAPIError(e) => driver::is_transient_windows_error(e),
+            _ => false,
+        }
+    }
+}
+
+/// The requests a [`Bus`] forwards, implemented by the driver and by the mock.
+///
+/// Metrics, logging of the facade and the notification threads are handled by [`Bus`],
+/// so a backend only has to carry out the request itself.
+pub(crate) trait Backend: Send + Sync {
+    /// Returns a description of where requests go, such as the device interface path.
+    fn device_path(&self) -> &str;
+
+    /// Checks the interface version with the bus and returns the accepted version.
+    fn driver_version(&self) -> Result<u32, BusError>;
+
+    /// Lists the virtual controllers attached to the bus, regardless of which client
+    /// plugged them in.
+    fn enumerate_children(&self) -> Result<Vec<BusChild>, BusError>;
+
+    fn plug(&self, target: &Target, serial_no: u32) -> Result<(), BusError>;
+
+    fn unplug(&self, serial_no: u32) -> Result<(), BusError>;
+
+    #[cfg(feature = "x360")]
+    fn update_x360(&self, serial_no: u32, report: &X360Report) -> Result<(), BusError>;
+
+    #[cfg(feature = "x360")]
+    fn x360_user_index(&self, serial_no: u32) -> Result<u32, BusError>;
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4(&self, serial_no: u32, report: &Ds4Report) -> Result<(), BusError>;
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4_ex(&self, serial_no: u32, report: &Ds4ReportEx) -> Result<(), BusError>;
+
+    /// Prepares to issue notification requests with `ioctl_code` for a target.
+    ///
+    /// Called on the registering thread, so that failures are reported to the caller
+    /// and notifications sent right after registering are not missed.
+    fn open_notifications(
+        self: Arc<Self>,
+        serial_no: u32,
+        ioctl_code: u32,
+    ) -> Result<Box<dyn NotificationSource>, BusError>;
+}
+
+/// Waits for host notifications of one target, on a notification thread.
+pub(crate) trait NotificationSource: Send {
+    /// Blocks until the next notification, and writes it into `request`.
+    fn next(&mut self, request: &mut dyn NotificationRequest) -> Result<(), BusError>;
 }
 
-// The Win32 handle is safe to send between threads
-unsafe impl Send for BusInner {}
-unsafe impl Sync for BusInner {}
+/// A notification request buffer, which the driver fills in place.
+pub(crate) trait NotificationRequest: Any {
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn as_mut_ptr(&mut self) -> *mut c_void;
+
+    #[cfg_attr(not(windows), allow(dead_code))]
+    fn size(&self) -> u32;
+
+    #[cfg_attr(not(feature = "mock"), allow(dead_code))]
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Copy + 'static> NotificationRequest for T {
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self as *mut T as *mut c_void
+    }
+
+    fn size(&self) -> u32 {
+        size_of::<T>() as u32
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
 
 #[derive(Clone)]
 pub(crate) struct Bus {
-    inner: Arc<BusInner>,
+    backend: Arc<dyn Backend>,
+    notification_priority: ThreadPriority,
+    metrics: Option<Arc<MetricsCounters>>,
 }
 
-impl Bus {
-    pub(crate) fn connect() -> Result<Self, BusError> {
-        unsafe {
-            let devices = SetupDiGetClassDevsW(
-                Some(&VIGEM_GUID as *const _),
-                None,
-                None,
-                DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
-            )?;
-
-            // Ensure the device info list is destroyed on every path.
-            struct DevInfoGuard(HDEVINFO);
-            impl Drop for DevInfoGuard {
-                fn drop(&mut self) {
-                    unsafe {
-                        let _ = SetupDiDestroyDeviceInfoList(self.0);
-                    }
-                }
-            }
-            let _guard = DevInfoGuard(devices);
-
-            for iface_result in DeviceInterfaceIterator::new(devices) {
-                let iface = iface_result?;
-                // get required device detail size
-                let mut needed: u32 = 0;
-                let _ = SetupDiGetDeviceInterfaceDetailW(
-                    devices,
-                    &iface as *const _,
-                    None,
-                    0,
-                    Some(&mut needed as *mut _),
-                    None,
-                );
+/// A virtual controller found as a child device of a ViGEm bus.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BusChild {
+    pub(crate) serial_no: u32,
+    pub(crate) kind: Option<TargetType>,
+    pub(crate) vendor_id: u16,
+    pub(crate) product_id: u16,
+}
 
-                if needed == 0 {
-                    continue;
-                }
+impl Bus {
+    /// Checks whether a ViGEm bus device interface is present, without opening it.
+    pub(crate) fn is_available() -> bool {
+        #[cfg(windows)]
+        return DriverBus::is_available();
 
-                let mut buf = vec![0u8; needed as usize];
-                let detail_ptr = buf.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
-                let cb_size_ptr = ptr::addr_of_mut!((*detail_ptr).cbSize);
-                *cb_size_ptr = size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
-
-                if SetupDiGetDeviceInterfaceDetailW(
-                    devices,
-                    &iface as *const _,
-                    Some(detail_ptr),
-                    needed,
-                    Some(&mut needed as *mut _),
-                    None,
-                )
-                .is_err()
-                {
-                    continue;
-                }
+        #[cfg(not(windows))]
+        false
+    }
 
-                // Try to open device handle
-                let handle = match CreateFileW(
-                    PCWSTR::from_raw((*detail_ptr).DevicePath.as_ptr()),
-                    (GENERIC_READ | GENERIC_WRITE).0,
-                    FILE_SHARE_READ | FILE_SHARE_WRITE,
-                    None,
-                    OPEN_EXISTING,
-                    FILE_ATTRIBUTE_NORMAL
-                        | FILE_FLAG_NO_BUFFERING
-                        | FILE_FLAG_WRITE_THROUGH
-                        | FILE_FLAG_OVERLAPPED,
-                    None,
-                ) {
-                    Ok(h) => h,
-                    Err(_) => continue,
-                };
-
-                // version
-                let version = CheckVersion {
-                    size: size_of::<CheckVersion>() as u32,
-                    version: 0x0001,
-                };
-                let mut transferred: u32 = 0;
-                if let Ok(()) = DeviceIoControl(
-                    handle,
-                    IOCTL_VIGEM_CHECK_VERSION,
-                    Some(&version as *const _ as *const c_void),
-                    version.size,
-                    None,
-                    0,
-                    Some(&mut transferred as *mut _),
-                    None,
-                ) {
-                    return Ok(Bus {
-                        inner: Arc::new(BusInner { handle }),
-                    });
-                } else {
-                    // Version mismatch
-                    let _ = CloseHandle(handle);
-                    //return Err(BusError::VersionMismatch);
-                }
-            }
+    /// Opens the first bus that accepts our interface version, or only the interface at
+    /// `bus_index` in enumeration order if given.
+    ///
+    /// ViGEmBus only exists on Windows, so elsewhere this always fails with
+    /// [`BusError::BusNotFound`].
+    pub(crate) fn connect(io_flags: IoFlags, bus_index: Option<usize>) -> Result<Self, BusError> {
+        #[cfg(windows)]
+        return DriverBus::connect(io_flags, bus_index).map(|bus| Self::new(Arc::new(bus)));
+
+        #[cfg(not(windows))]
+        {
+            let _ = (io_flags, bus_index);
+            Err(BusError::BusNotFound)
         }
+    }
 
-        Err(BusError::BusNotFound)
+    /// Creates a bus that records its requests in `mock` instead of opening the driver.
+    #[cfg(feature = "mock")]
+    pub(crate) fn mock(mock: MockBus) -> Self {
+        Self::new(Arc::new(mock))
     }
 
-    pub(crate) fn plug(&self, target: &Target, serial_no: u32) -> Result<(), BusError> {
-        let plugin = PluginTarget {
-            size: size_of::<PluginTarget>() as u32,
-            serial_no,
-            target_type: target.kind,
-            vendor_id: target.vendor_id,
-            product_id: target.product_id,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_VIGEM_PLUGIN_TARGET,
-                Some(&plugin as *const _ as *const c_void),
-                plugin.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
+    #[cfg(any(windows, feature = "mock"))]
+    fn new(backend: Arc<dyn Backend>) -> Self {
+        Bus {
+            backend,
+            notification_priority: ThreadPriority::Normal,
+            metrics: None,
         }
+    }
 
-        // This 'wait device ready' call that is supposed to block until the controller
-        // can receive updates doesn't seem to properly work...
-        let wait_ready = WaitDeviceReady {
-            size: size_of::<WaitDeviceReady>() as u32,
-            serial_no,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_VIGEM_WAIT_DEVICE_READY,
-                Some(&wait_ready as *const _ as *const c_void),
-                wait_ready.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
-        }
+    /// Lists the virtual controllers attached to the bus, regardless of which client
+    /// plugged them in.
+    pub(crate) fn enumerate_children(&self) -> Result<Vec<BusChild>, BusError> {
+        self.backend.enumerate_children()
+    }
 
+    pub(crate) fn plug(&self, target: &Target, serial_no: u32) -> Result<(), BusError> {
+        self.backend.plug(target, serial_no)?;
+        self.count(|metrics| &metrics.plug_count);
         Ok(())
     }
 
     pub(crate) fn unplug(&self, serial_no: u32) -> Result<(), BusError> {
-        let unplug = UnPlugTarget {
-            size: size_of::<UnPlugTarget>() as u32,
-            serial_no,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_VIGEM_UNPLUG_TARGET,
-                Some(&unplug as *const _ as *const c_void),
-                unplug.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
+        self.backend.unplug(serial_no)?;
+        self.count(|metrics| &metrics.unplug_count);
+        Ok(())
+    }
+
+    /// Sets the priority applied to notification threads spawned through this bus.
+    pub(crate) fn with_notification_priority(mut self, priority: ThreadPriority) -> Self {
+        self.notification_priority = priority;
+        self
+    }
+
+    /// Enables activity counting, shared by all clones of this bus.
+    pub(crate) fn with_metrics(mut self, metrics: Arc<MetricsCounters>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the activity counters, if they were enabled.
+    pub(crate) fn metrics(&self) -> Option<&MetricsCounters> {
+        self.metrics.as_deref()
+    }
+
+    /// Increments one of the activity counters, if they were enabled.
+    #[inline]
+    fn count(&self, counter: impl FnOnce(&MetricsCounters) -> &AtomicU64) {
+        if let Some(metrics) = &self.metrics {
+            counter(metrics).fetch_add(1, Ordering::Relaxed);
         }
+    }
 
-        Ok(())
+    /// Returns the device interface path this bus was opened with.
+    pub(crate) fn device_path(&self) -> &str {
+        self.backend.device_path()
+    }
+
+    pub(crate) fn driver_version(&self) -> Result<u32, BusError> {
+        self.backend.driver_version()
     }
 
     #[cfg(feature = "x360")]
     pub(crate) fn update_x360(&self, serial_no: u32, report: &X360Report) -> Result<(), BusError> {
-        let submit_report = XusbSubmitReport {
-            size: size_of::<XusbSubmitReport>() as u32,
-            serial_no,
-            report: *report,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_XUSB_SUBMIT_REPORT,
-                Some(&submit_report as *const _ as *const c_void),
-                submit_report.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
-        }
-
-        Ok(())
+        self.backend
+            .update_x360(serial_no, report)
+            .inspect(|_| self.count(|metrics| &metrics.reports_submitted))
     }
 
     #[cfg(feature = "ds4")]
     pub(crate) fn update_ds4(&self, serial_no: u32, report: &Ds4Report) -> Result<(), BusError> {
-        let submit_report = Ds4SubmitReport {
-            size: size_of::<Ds4SubmitReport>() as u32,
-            serial_no,
-            report: *report,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_DS4_SUBMIT_REPORT,
-                Some(&submit_report as *const _ as *const c_void),
-                submit_report.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
-        }
-
-        Ok(())
+        self.backend
+            .update_ds4(serial_no, report)
+            .inspect(|_| self.count(|metrics| &metrics.reports_submitted))
     }
 
     #[cfg(feature = "ds4")]
@@ -314,102 +271,79 @@ impl Bus {
         serial_no: u32,
         report: &Ds4ReportEx,
     ) -> Result<(), BusError> {
-        let submit_report = Ds4SubmitReportEx {
-            size: size_of::<Ds4SubmitReportEx>() as u32,
-            serial_no,
-            report: *report,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            // Note: We use the same IOCTL as the basic DS4 report.
-            // The driver determines the report type by the size field apparently
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_DS4_SUBMIT_REPORT,
-                Some(&submit_report as *const _ as *const c_void),
-                submit_report.size,
-                None,
-                0,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
-        }
-
-        Ok(())
+        self.backend
+            .update_ds4_ex(serial_no, report)
+            .inspect(|_| self.count(|metrics| &metrics.reports_submitted))
     }
 
-    pub(crate) fn spawn_notification_thread<W: NotificationWorker>(
+    pub(crate) fn spawn_notification_thread<W, S>(
         &self,
         serial_no: u32,
-        sender: Sender<Result<W::Notification, BusError>>,
-    ) -> Result<(), BusError> {
+        sender: S,
+    ) -> Result<(), BusError>
+    where
+        W: NotificationWorker,
+        S: NotificationSink<W::Notification>,
+    {
+        let mut source = Arc::clone(&self.backend).open_notifications(serial_no, W::IOCTL_CODE)?;
         let bus = self.clone();
+        let priority = self.notification_priority;
 
         // Create a dedicated channel for startup synchronization.
         let (sync_tx, sync_rx) = mpsc::channel::<Result<(), BusError>>();
 
-        std::thread::spawn(move || {
-            // This is simply to try the fallible operation before starting the loop
-            if let Err(e) = OverlappedCall::new() {
-                let _ = sync_tx.send(Err(e.into()));
-                return;
-            }
-
-            if sync_tx.send(Ok(())).is_err() {
-                return;
-            }
-
-            loop {
-                let mut request = W::create_request(serial_no);
-                let mut call = match OverlappedCall::new() {
-                    Ok(c) => c,
-                    Err(e) => {
-                        let _ = sender.send(Err(e.into()));
-                        return;
-                    }
-                };
-
-                let req_size = size_of::<W::Request>() as u32;
-
-                unsafe {
-                    let _ = DeviceIoControl(
-                        bus.inner.handle,
-                        W::IOCTL_CODE,
-                        Some(&request as *const _ as *const c_void),
-                        req_size,
-                        Some(&mut request as *mut _ as *mut c_void),
-                        req_size,
-                        Some(call.transferred_ptr()),
-                        Some(call.as_mut_overlapped()),
-                    );
+        let thread_name = format!("vigem-{}-{}", W::THREAD_LABEL, serial_no);
+
+        std::thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || {
+                if let Err(e) = apply_thread_priority(priority) {
+                    let _ = sync_tx.send(Err(e));
+                    return;
                 }
 
-                match call.wait(bus.inner.handle) {
-                    Ok(_) => {
-                        let notification = W::process_response(&request);
-                        if sender.send(Ok(notification)).is_err() {
+                if sync_tx.send(Ok(())).is_err() {
+                    return;
+                }
+                trace!(
+                    "Notification thread {} started for serial {serial_no}",
+                    W::THREAD_LABEL
+                );
+
+                loop {
+                    let mut request = W::create_request(serial_no);
+
+                    match source.next(&mut request) {
+                        Ok(()) => {
+                            bus.count(|metrics| &metrics.notifications_received);
+
+                            let notification = W::process_response(&request);
+                            if sender.send(Ok(notification)).is_err() {
+                                trace!(
+                                    "Notification thread {} for serial {serial_no} stopped, \
+                                     the receiver was dropped",
+                                    W::THREAD_LABEL
+                                );
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            debug!(
+                                "Notification thread {} for serial {serial_no} stopped: {e}",
+                                W::THREAD_LABEL
+                            );
+                            let _ = sender.send(Err(e));
                             break;
                         }
                     }
-                    Err(e) => {
-                        let _ = sender.send(Err(e.into()));
-                        break;
-                    }
                 }
-            }
-        });
+            })?;
 
         match sync_rx.recv() {
             Ok(Ok(())) => Ok(()),
             Ok(Err(e)) => Err(e),
-            Err(_) => Err(BusError::WindowsAPIError(windows::core::Error::new(
-                windows::Win32::Foundation::E_FAIL,
-                "Notification thread panicked during startup.",
-            ))),
+            // The thread exited without reporting back, i.e. it panicked during startup.
+            Err(_) => Err(BusError::NotificationThreadStopped),
         }
     }
 
@@ -417,98 +351,65 @@ impl Bus {
     pub(crate) fn start_x360_notification_thread(
         &self,
         serial_no: u32,
-        sender: Sender<Result<X360Notification, BusError>>,
+        sender: impl NotificationSink<X360Notification>,
     ) -> Result<(), BusError> {
-        self.spawn_notification_thread::<X360NotificationWorker>(serial_no, sender)
+        self.spawn_notification_thread::<X360NotificationWorker, _>(serial_no, sender)
+    }
+
+    #[cfg(feature = "x360")]
+    pub(crate) fn start_x360_raw_notification_thread(
+        &self,
+        serial_no: u32,
+        sender: impl NotificationSink<X360NotificationBuffer>,
+    ) -> Result<(), BusError> {
+        self.spawn_notification_thread::<X360RawNotificationWorker, _>(serial_no, sender)
     }
 
     #[cfg(feature = "ds4")]
     pub(crate) fn start_ds4_notification_thread(
         &self,
         serial_no: u32,
-        sender: Sender<Result<Ds4Notification, BusError>>,
+        sender: impl NotificationSink<Ds4Notification>,
     ) -> Result<(), BusError> {
-        self.spawn_notification_thread::<Ds4NotificationWorker>(serial_no, sender)
+        self.spawn_notification_thread::<Ds4NotificationWorker, _>(serial_no, sender)
     }
 
     #[cfg(feature = "ds4")]
     pub(crate) fn start_ds4_output_thread(
         &self,
         serial_no: u32,
-        sender: Sender<Result<Ds4OutputBuffer, BusError>>,
+        sender: impl NotificationSink<Ds4OutputBuffer>,
     ) -> Result<(), BusError> {
-        self.spawn_notification_thread::<Ds4OutputWorker>(serial_no, sender)
+        self.spawn_notification_thread::<Ds4OutputWorker, _>(serial_no, sender)
+    }
+
+    #[cfg(feature = "ds4")]
+    pub(crate) fn start_ds4_parsed_output_thread(
+        &self,
+        serial_no: u32,
+        sender: impl NotificationSink<Ds4FullOutput>,
+    ) -> Result<(), BusError> {
+        self.spawn_notification_thread::<Ds4ParsedOutputWorker, _>(serial_no, sender)
     }
 
     #[cfg(feature = "x360")]
     pub(crate) fn get_x360_user_index(&self, serial_no: u32) -> Result<u32, BusError> {
-        let mut get_index = XusbGetUserIndex {
-            size: size_of::<XusbGetUserIndex>() as u32,
-            serial_no,
-            user_index: 0,
-        };
-
-        unsafe {
-            let mut call = OverlappedCall::new()?;
-
-            let _ = DeviceIoControl(
-                self.inner.handle,
-                IOCTL_XUSB_GET_USER_INDEX,
-                Some(&get_index as *const _ as *const c_void),
-                get_index.size,
-                Some(&mut get_index as *mut _ as *mut c_void),
-                get_index.size,
-                Some(call.transferred_ptr()),
-                Some(call.as_mut_overlapped()),
-            );
-
-            call.wait(self.inner.handle)?;
-        }
-
-        Ok(get_index.user_index)
+        self.backend.x360_user_index(serial_no)
     }
 }
 
-// HELPER ITERATOR
+// THREAD PRIORITY
 
-pub struct DeviceInterfaceIterator {
-    devices: HDEVINFO,
-    index: u32,
-}
-
-impl DeviceInterfaceIterator {
-    pub fn new(devices: HDEVINFO) -> Self {
-        DeviceInterfaceIterator { devices, index: 0 }
-    }
-}
+/// Applies `priority` to the calling thread. [`ThreadPriority::Normal`] is a no-op.
+///
+/// Thread priorities are only applied on Windows, where the driver runs.
+fn apply_thread_priority(priority: ThreadPriority) -> Result<(), BusError> {
+    #[cfg(windows)]
+    return driver::apply_thread_priority(priority);
 
-impl Iterator for DeviceInterfaceIterator {
-    type Item = Result<SP_DEVICE_INTERFACE_DATA, windows::core::Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut data = SP_DEVICE_INTERFACE_DATA {
-            cbSize: size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
-            ..Default::default()
-        };
-
-        // Perform the API call
-        let result = unsafe {
-            SetupDiEnumDeviceInterfaces(
-                self.devices,
-                None,
-                &VIGEM_GUID as *const _,
-                self.index,
-                &mut data as *mut _,
-            )
-        };
-
-        match result {
-            Ok(_) => {
-                self.index += 1;
-                Some(Ok(data))
-            }
-            Err(e) if e.code() == ERROR_NO_MORE_ITEMS.to_hresult() => None,
-            Err(e) => Some(Err(e)), // Propagate other errors
-        }
+    #[cfg(not(windows))]
+    {
+        let _ = priority;
+        Ok(())
     }
 }
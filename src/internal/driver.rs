@@ -0,0 +1,731 @@
+//! The ViGEmBus driver backend, talking to the bus device through Win32 IOCTLs.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::Arc;
+
+use windows::Win32::Devices::DeviceAndDriverInstallation::{
+    CM_DRP_COMPATIBLEIDS, CM_Get_Child, CM_Get_DevNode_Registry_PropertyW, CM_Get_Device_IDW,
+    CM_Get_Sibling, CR_SUCCESS, DIGCF_DEVICEINTERFACE, DIGCF_PRESENT, HDEVINFO, MAX_DEVICE_ID_LEN,
+    SP_DEVICE_INTERFACE_DATA, SP_DEVICE_INTERFACE_DETAIL_DATA_W, SP_DEVINFO_DATA,
+    SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInfo, SetupDiEnumDeviceInterfaces,
+    SetupDiGetClassDevsW, SetupDiGetDeviceInterfaceDetailW,
+};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_BUSY, ERROR_INVALID_PARAMETER, ERROR_NO_MORE_ITEMS, ERROR_NOT_READY,
+    ERROR_SEM_TIMEOUT, ERROR_TIMEOUT, GENERIC_READ, GENERIC_WRITE, HANDLE,
+};
+use windows::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_ATTRIBUTE_NORMAL, FILE_FLAG_NO_BUFFERING, FILE_FLAG_OVERLAPPED,
+    FILE_FLAG_WRITE_THROUGH, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+use windows::Win32::System::Threading::{
+    GetCurrentThread, SetThreadPriority, THREAD_PRIORITY_ABOVE_NORMAL,
+    THREAD_PRIORITY_TIME_CRITICAL,
+};
+use windows::core::{GUID, PCWSTR};
+
+use crate::client::{IoFlags, ThreadPriority};
+#[cfg(feature = "ds4")]
+use crate::controller::ds4::{Ds4Report, Ds4ReportEx, Ds4SubmitReport, Ds4SubmitReportEx};
+#[cfg(feature = "x360")]
+use crate::controller::x360::{X360Report, XusbSubmitReport};
+use crate::internal::bus::{Backend, BusChild, BusError, NotificationRequest, NotificationSource};
+use crate::internal::ioctl::*;
+use crate::internal::logging::{debug, trace, warn};
+use crate::internal::overlapped::OverlappedCall;
+use crate::target::{Target, TargetType};
+
+/// Returns `true` if the driver rejected a plugin request because the requested
+/// serial number is already in use.
+pub(crate) fn is_slot_in_use_windows_error(error: &windows::core::Error) -> bool {
+    // The bus answers a duplicate serial with `STATUS_INVALID_PARAMETER`.
+    error.code() == ERROR_INVALID_PARAMETER.to_hresult()
+}
+
+/// Returns `true` for Windows error codes that signal a busy or not-yet-ready device.
+pub(crate) fn is_transient_windows_error(error: &windows::core::Error) -> bool {
+    let code = error.code();
+    code == ERROR_BUSY.to_hresult()
+        || code == ERROR_NOT_READY.to_hresult()
+        || code == ERROR_SEM_TIMEOUT.to_hresult()
+        || code == ERROR_TIMEOUT.to_hresult()
+}
+
+const VIGEM_GUID: GUID = GUID::from_values(
+    0x96E42B22,
+    0xF5E9,
+    0x42F8,
+    [0xB0, 0x43, 0xED, 0x0F, 0x93, 0x2F, 0x01, 0x4F],
+);
+
+/// An open handle to a ViGEm bus device.
+pub(crate) struct DriverBus {
+    handle: HANDLE,
+    // The interface path the handle was opened with, kept for diagnostics.
+    device_path: String,
+}
+
+impl Drop for DriverBus {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(self.handle);
+        }
+    }
+}
+
+// The Win32 handle is safe to send between threads
+unsafe impl Send for DriverBus {}
+unsafe impl Sync for DriverBus {}
+
+/// Destroys a device info list when dropped.
+struct DevInfoGuard(HDEVINFO);
+
+impl Drop for DevInfoGuard {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = SetupDiDestroyDeviceInfoList(self.0);
+        }
+    }
+}
+
+impl DriverBus {
+    /// Checks whether a ViGEm bus device interface is present, without opening it.
+    pub(crate) fn is_available() -> bool {
+        unsafe {
+            let Ok(devices) = SetupDiGetClassDevsW(
+                Some(&VIGEM_GUID as *const _),
+                None,
+                None,
+                DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+            ) else {
+                return false;
+            };
+            let _guard = DevInfoGuard(devices);
+
+            matches!(DeviceInterfaceIterator::new(devices).next(), Some(Ok(_)))
+        }
+    }
+
+    /// Opens the first bus that accepts our interface version, or only the interface at
+    /// `bus_index` in enumeration order if given.
+    pub(crate) fn connect(io_flags: IoFlags, bus_index: Option<usize>) -> Result<Self, BusError> {
+        let mut file_flags = FILE_ATTRIBUTE_NORMAL | FILE_FLAG_OVERLAPPED;
+        if io_flags.contains(IoFlags::NO_BUFFERING) {
+            file_flags |= FILE_FLAG_NO_BUFFERING;
+        }
+        if io_flags.contains(IoFlags::WRITE_THROUGH) {
+            file_flags |= FILE_FLAG_WRITE_THROUGH;
+        }
+
+        unsafe {
+            let devices = SetupDiGetClassDevsW(
+                Some(&VIGEM_GUID as *const _),
+                None,
+                None,
+                DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+            )?;
+
+            // Ensure the device info list is destroyed on every path.
+            let _guard = DevInfoGuard(devices);
+
+            // Set if a bus was found but refused our interface version.
+            let mut version_mismatch = false;
+
+            for (index, iface_result) in DeviceInterfaceIterator::new(devices).enumerate() {
+                if bus_index.is_some_and(|wanted| wanted != index) {
+                    continue;
+                }
+                let iface = iface_result?;
+                // get required device detail size
+                let mut needed: u32 = 0;
+                let _ = SetupDiGetDeviceInterfaceDetailW(
+                    devices,
+                    &iface as *const _,
+                    None,
+                    0,
+                    Some(&mut needed as *mut _),
+                    None,
+                );
+
+                if needed == 0 {
+                    continue;
+                }
+
+                let mut buf = vec![0u8; needed as usize];
+                let detail_ptr = buf.as_mut_ptr() as *mut SP_DEVICE_INTERFACE_DETAIL_DATA_W;
+                let cb_size_ptr = ptr::addr_of_mut!((*detail_ptr).cbSize);
+                *cb_size_ptr = size_of::<SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
+
+                if SetupDiGetDeviceInterfaceDetailW(
+                    devices,
+                    &iface as *const _,
+                    Some(detail_ptr),
+                    needed,
+                    Some(&mut needed as *mut _),
+                    None,
+                )
+                .is_err()
+                {
+                    continue;
+                }
+
+                // Try to open device handle
+                let device_path = PCWSTR::from_raw((*detail_ptr).DevicePath.as_ptr());
+                let handle = match CreateFileW(
+                    device_path,
+                    (GENERIC_READ | GENERIC_WRITE).0,
+                    FILE_SHARE_READ | FILE_SHARE_WRITE,
+                    None,
+                    OPEN_EXISTING,
+                    file_flags,
+                    None,
+                ) {
+                    Ok(h) => h,
+                    Err(_) => continue,
+                };
+
+                // version
+                let version = CheckVersion {
+                    size: size_of::<CheckVersion>() as u32,
+                    version: VIGEM_COMMON_VERSION,
+                };
+                let mut transferred: u32 = 0;
+                if let Ok(()) = DeviceIoControl(
+                    handle,
+                    IOCTL_VIGEM_CHECK_VERSION,
+                    Some(&version as *const _ as *const c_void),
+                    version.size,
+                    None,
+                    0,
+                    Some(&mut transferred as *mut _),
+                    None,
+                ) {
+                    let device_path = String::from_utf16_lossy(device_path.as_wide());
+                    debug!("Connected to ViGEm bus at {device_path}");
+                    return Ok(DriverBus {
+                        handle,
+                        device_path,
+                    });
+                } else {
+                    // Version mismatch, keep looking in case another bus accepts it.
+                    warn!(
+                        "ViGEm bus at {} rejected interface version {VIGEM_COMMON_VERSION:#06x}",
+                        String::from_utf16_lossy(device_path.as_wide())
+                    );
+                    let _ = CloseHandle(handle);
+                    version_mismatch = true;
+                }
+            }
+
+            if version_mismatch {
+                return Err(BusError::VersionMismatch);
+            }
+        }
+
+        Err(BusError::BusNotFound)
+    }
+}
+
+impl Backend for DriverBus {
+    fn device_path(&self) -> &str {
+        &self.device_path
+    }
+
+    fn driver_version(&self) -> Result<u32, BusError> {
+        let version = CheckVersion {
+            size: size_of::<CheckVersion>() as u32,
+            version: VIGEM_COMMON_VERSION,
+        };
+
+        // The driver writes nothing back, it only accepts or rejects the version sent.
+        unsafe {
+            let mut call = OverlappedCall::new()?;
+
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_VIGEM_CHECK_VERSION,
+                Some(&version as *const _ as *const c_void),
+                version.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle)?;
+        }
+
+        Ok(VIGEM_COMMON_VERSION)
+    }
+
+    /// Lists the virtual controllers attached to every ViGEm bus on the system,
+    /// regardless of which client plugged them in.
+    ///
+    /// The driver has no IOCTL for this, so the children of the bus device node are
+    /// walked with the configuration manager instead.
+    fn enumerate_children(&self) -> Result<Vec<BusChild>, BusError> {
+        let mut children = Vec::new();
+
+        unsafe {
+            let devices = SetupDiGetClassDevsW(
+                Some(&VIGEM_GUID as *const _),
+                None,
+                None,
+                DIGCF_PRESENT | DIGCF_DEVICEINTERFACE,
+            )?;
+            let _guard = DevInfoGuard(devices);
+
+            for index in 0.. {
+                let mut info = SP_DEVINFO_DATA {
+                    cbSize: size_of::<SP_DEVINFO_DATA>() as u32,
+                    ..Default::default()
+                };
+
+                match SetupDiEnumDeviceInfo(devices, index, &mut info as *mut _) {
+                    Ok(()) => {}
+                    Err(e) if e.code() == ERROR_NO_MORE_ITEMS.to_hresult() => break,
+                    Err(e) => return Err(e.into()),
+                }
+
+                let mut dev_inst = 0;
+                let mut status = CM_Get_Child(&mut dev_inst, info.DevInst, 0);
+                while status == CR_SUCCESS {
+                    if let Some(child) = read_bus_child(dev_inst) {
+                        children.push(child);
+                    }
+                    status = CM_Get_Sibling(&mut dev_inst, dev_inst, 0);
+                }
+            }
+        }
+
+        Ok(children)
+    }
+
+    fn plug(&self, target: &Target, serial_no: u32) -> Result<(), BusError> {
+        let plugin = PluginTarget {
+            size: size_of::<PluginTarget>() as u32,
+            serial_no,
+            target_type: target.kind,
+            vendor_id: target.vendor_id,
+            product_id: target.product_id,
+        };
+
+        unsafe {
+            let mut call = OverlappedCall::new()?;
+
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_VIGEM_PLUGIN_TARGET,
+                Some(&plugin as *const _ as *const c_void),
+                plugin.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle).inspect_err(|e| {
+                trace!("IOCTL_VIGEM_PLUGIN_TARGET for serial {serial_no} failed: {e}")
+            })?;
+        }
+
+        // This 'wait device ready' call that is supposed to block until the controller
+        // can receive updates doesn't seem to properly work, so it can be skipped.
+        if !target.skip_driver_wait {
+            let wait_ready = WaitDeviceReady {
+                size: size_of::<WaitDeviceReady>() as u32,
+                serial_no,
+            };
+
+            unsafe {
+                let mut call = OverlappedCall::new()?;
+
+                let _ = DeviceIoControl(
+                    self.handle,
+                    IOCTL_VIGEM_WAIT_DEVICE_READY,
+                    Some(&wait_ready as *const _ as *const c_void),
+                    wait_ready.size,
+                    None,
+                    0,
+                    Some(call.transferred_ptr()),
+                    Some(call.as_mut_overlapped()),
+                );
+
+                call.wait(self.handle).inspect_err(|e| {
+                    warn!("IOCTL_VIGEM_WAIT_DEVICE_READY for serial {serial_no} failed: {e}")
+                })?;
+            }
+        }
+
+        debug!(
+            "Plugged in {:?} target with serial {serial_no} ({:#06x}:{:#06x})",
+            target.kind, target.vendor_id, target.product_id
+        );
+        Ok(())
+    }
+
+    fn unplug(&self, serial_no: u32) -> Result<(), BusError> {
+        let unplug = UnPlugTarget {
+            size: size_of::<UnPlugTarget>() as u32,
+            serial_no,
+        };
+
+        unsafe {
+            let mut call = OverlappedCall::new()?;
+
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_VIGEM_UNPLUG_TARGET,
+                Some(&unplug as *const _ as *const c_void),
+                unplug.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle).inspect_err(|e| {
+                warn!("IOCTL_VIGEM_UNPLUG_TARGET for serial {serial_no} failed: {e}")
+            })?;
+        }
+
+        debug!("Unplugged target with serial {serial_no}");
+        Ok(())
+    }
+
+    #[cfg(feature = "x360")]
+    fn update_x360(&self, serial_no: u32, report: &X360Report) -> Result<(), BusError> {
+        let submit_report = XusbSubmitReport {
+            size: size_of::<XusbSubmitReport>() as u32,
+            serial_no,
+            report: *report,
+        };
+
+        with_cached_call(|call| unsafe {
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_XUSB_SUBMIT_REPORT,
+                Some(&submit_report as *const _ as *const c_void),
+                submit_report.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle)?;
+            Ok(())
+        })
+        .inspect_err(|e| trace!("IOCTL_XUSB_SUBMIT_REPORT for serial {serial_no} failed: {e}"))
+    }
+
+    #[cfg(feature = "x360")]
+    fn x360_user_index(&self, serial_no: u32) -> Result<u32, BusError> {
+        let mut get_index = XusbGetUserIndex {
+            size: size_of::<XusbGetUserIndex>() as u32,
+            serial_no,
+            user_index: 0,
+        };
+
+        unsafe {
+            let mut call = OverlappedCall::new()?;
+
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_XUSB_GET_USER_INDEX,
+                Some(&get_index as *const _ as *const c_void),
+                get_index.size,
+                Some(&mut get_index as *mut _ as *mut c_void),
+                get_index.size,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle)?;
+        }
+
+        Ok(get_index.user_index)
+    }
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4(&self, serial_no: u32, report: &Ds4Report) -> Result<(), BusError> {
+        // Building the submit struct per call is a small stack write. Reusing a per-thread
+        // struct and only overwriting the report measured slower, as the thread-local
+        // access costs more than writing `size` (see `benches/ds4_submit.rs`), so only
+        // the overlapped call (an event handle) is cached.
+        let submit_report = Ds4SubmitReport {
+            size: size_of::<Ds4SubmitReport>() as u32,
+            serial_no,
+            report: *report,
+        };
+
+        with_cached_call(|call| unsafe {
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_DS4_SUBMIT_REPORT,
+                Some(&submit_report as *const _ as *const c_void),
+                submit_report.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle)?;
+            Ok(())
+        })
+        .inspect_err(|e| trace!("IOCTL_DS4_SUBMIT_REPORT for serial {serial_no} failed: {e}"))
+    }
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4_ex(&self, serial_no: u32, report: &Ds4ReportEx) -> Result<(), BusError> {
+        let submit_report = Ds4SubmitReportEx {
+            size: size_of::<Ds4SubmitReportEx>() as u32,
+            serial_no,
+            report: *report,
+        };
+
+        with_cached_call(|call| unsafe {
+            // Note: We use the same IOCTL as the basic DS4 report. The driver tells the
+            // extended report apart by the size field, and prefixes it with the USB
+            // report ID (0x01) itself, so there is no way to send a Bluetooth report.
+            let _ = DeviceIoControl(
+                self.handle,
+                IOCTL_DS4_SUBMIT_REPORT,
+                Some(&submit_report as *const _ as *const c_void),
+                submit_report.size,
+                None,
+                0,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+
+            call.wait(self.handle)?;
+            Ok(())
+        })
+        .inspect_err(|e| {
+            trace!("IOCTL_DS4_SUBMIT_REPORT (extended) for serial {serial_no} failed: {e}")
+        })
+    }
+
+    fn open_notifications(
+        self: Arc<Self>,
+        _serial_no: u32,
+        ioctl_code: u32,
+    ) -> Result<Box<dyn NotificationSource>, BusError> {
+        // This is simply to try the fallible operation before starting the loop
+        OverlappedCall::new()?;
+
+        Ok(Box::new(DriverNotifications {
+            bus: self,
+            ioctl_code,
+        }))
+    }
+}
+
+/// Issues notification requests with one IOCTL code, one at a time.
+struct DriverNotifications {
+    bus: Arc<DriverBus>,
+    ioctl_code: u32,
+}
+
+impl NotificationSource for DriverNotifications {
+    fn next(&mut self, request: &mut dyn NotificationRequest) -> Result<(), BusError> {
+        let mut call = OverlappedCall::new()?;
+        let size = request.size();
+        let buffer = request.as_mut_ptr();
+
+        unsafe {
+            let _ = DeviceIoControl(
+                self.bus.handle,
+                self.ioctl_code,
+                Some(buffer as *const c_void),
+                size,
+                Some(buffer),
+                size,
+                Some(call.transferred_ptr()),
+                Some(call.as_mut_overlapped()),
+            );
+        }
+
+        call.wait(self.bus.handle)?;
+        Ok(())
+    }
+}
+
+// THREAD PRIORITY
+
+/// Applies `priority` to the calling thread. [`ThreadPriority::Normal`] is a no-op.
+pub(crate) fn apply_thread_priority(priority: ThreadPriority) -> Result<(), BusError> {
+    let priority = match priority {
+        ThreadPriority::Normal => return Ok(()),
+        ThreadPriority::AboveNormal => THREAD_PRIORITY_ABOVE_NORMAL,
+        ThreadPriority::TimeCritical => THREAD_PRIORITY_TIME_CRITICAL,
+    };
+
+    unsafe { SetThreadPriority(GetCurrentThread(), priority)? };
+    Ok(())
+}
+
+// BUS CHILD ENUMERATION
+
+/// Reads the identity of a bus child device node, skipping nodes that don't look
+/// like ViGEm targets.
+///
+/// # Safety
+/// `dev_inst` must be a device instance handle obtained from the configuration manager.
+unsafe fn read_bus_child(dev_inst: u32) -> Option<BusChild> {
+    let mut id = [0u16; MAX_DEVICE_ID_LEN as usize + 1];
+    if unsafe { CM_Get_Device_IDW(dev_inst, &mut id, 0) } != CR_SUCCESS {
+        return None;
+    }
+
+    let (vendor_id, product_id, serial_no) = parse_device_id(&from_wide(&id))?;
+
+    let mut compatible_ids = [0u16; 512];
+    let mut len = size_of_val(&compatible_ids) as u32;
+    let status = unsafe {
+        CM_Get_DevNode_Registry_PropertyW(
+            dev_inst,
+            CM_DRP_COMPATIBLEIDS,
+            None,
+            Some(compatible_ids.as_mut_ptr() as *mut c_void),
+            &mut len,
+            0,
+        )
+    };
+    let kind = if status == CR_SUCCESS {
+        kind_from_compatible_ids(&from_wide(&compatible_ids[..len as usize / 2]))
+    } else {
+        None
+    };
+
+    Some(BusChild {
+        serial_no,
+        kind,
+        vendor_id,
+        product_id,
+    })
+}
+
+/// Converts a (multi-)string UTF-16 buffer into a `String`, keeping the inner NULs.
+fn from_wide(buf: &[u16]) -> String {
+    let end = buf
+        .windows(2)
+        .position(|w| w == [0, 0])
+        .unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..end])
+}
+
+/// Extracts VID, PID and serial number from a device ID such as
+/// `USB\VID_045E&PID_028E\2&1d3b2c4&0&01`.
+///
+/// The bus assigns the serial number as the instance ID, which ends up as the
+/// last `&`-separated component of the device ID.
+fn parse_device_id(id: &str) -> Option<(u16, u16, u32)> {
+    let id = id.to_ascii_uppercase();
+    let mut parts = id.split('\\');
+    let _enumerator = parts.next()?;
+    let hardware = parts.next()?;
+    let instance = parts.next()?;
+
+    let field = |prefix: &str| {
+        hardware
+            .split('&')
+            .find_map(|part| part.strip_prefix(prefix))
+            .and_then(|value| u16::from_str_radix(value, 16).ok())
+    };
+    let serial_no = instance.rsplit('&').next()?.parse().ok()?;
+
+    Some((field("VID_")?, field("PID_")?, serial_no))
+}
+
+/// Tells the target type apart by its USB class, since VID and PID can be customized.
+fn kind_from_compatible_ids(ids: &str) -> Option<TargetType> {
+    let ids = ids.to_ascii_uppercase();
+    if ids.contains("CLASS_FF&SUBCLASS_5D") {
+        Some(TargetType::Xbox360)
+    } else if ids.contains("CLASS_03") {
+        Some(TargetType::DualShock4)
+    } else {
+        None
+    }
+}
+
+// CACHED OVERLAPPED CALL
+
+thread_local! {
+    // Report submission is the hot path (often 60Hz+ per controller), so each thread
+    // keeps one `OverlappedCall` around instead of creating and closing an event per call.
+    static SUBMIT_CALL: RefCell<Option<OverlappedCall>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with this thread's cached `OverlappedCall`, creating it on first use.
+///
+/// If `f` fails, the call is discarded so a possibly inconsistent event is never reused.
+fn with_cached_call<R>(
+    f: impl FnOnce(&mut OverlappedCall) -> Result<R, BusError>,
+) -> Result<R, BusError> {
+    SUBMIT_CALL.with(|slot| {
+        let mut call = match slot.borrow_mut().take() {
+            Some(mut call) => {
+                call.reset()?;
+                call
+            }
+            None => OverlappedCall::new()?,
+        };
+
+        let result = f(&mut call);
+        if result.is_ok() {
+            *slot.borrow_mut() = Some(call);
+        }
+        result
+    })
+}
+
+// HELPER ITERATOR
+
+pub struct DeviceInterfaceIterator {
+    devices: HDEVINFO,
+    index: u32,
+}
+
+impl DeviceInterfaceIterator {
+    pub fn new(devices: HDEVINFO) -> Self {
+        DeviceInterfaceIterator { devices, index: 0 }
+    }
+}
+
+impl Iterator for DeviceInterfaceIterator {
+    type Item = Result<SP_DEVICE_INTERFACE_DATA, windows::core::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut data = SP_DEVICE_INTERFACE_DATA {
+            cbSize: size_of::<SP_DEVICE_INTERFACE_DATA>() as u32,
+            ..Default::default()
+        };
+
+        // Perform the API call
+        let result = unsafe {
+            SetupDiEnumDeviceInterfaces(
+                self.devices,
+                None,
+                &VIGEM_GUID as *const _,
+                self.index,
+                &mut data as *mut _,
+            )
+        };
+
+        match result {
+            Ok(_) => {
+                self.index += 1;
+                Some(Ok(data))
+            }
+            Err(e) if e.code() == ERROR_NO_MORE_ITEMS.to_hresult() => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
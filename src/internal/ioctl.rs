@@ -1,16 +1,20 @@
-use windows::Win32::System::Ioctl::{
-    FILE_DEVICE_BUS_EXTENDER, FILE_READ_ACCESS, FILE_WRITE_ACCESS, METHOD_BUFFERED,
-};
-
 use crate::target::TargetType;
 
 const fn ctl_code(device_type: u32, function: u32, method: u32, access: u32) -> u32 {
     (device_type << 16) | (access << 14) | (function << 2) | method
 }
 
-const FILE_DEVICE_BUSENUM: u32 = FILE_DEVICE_BUS_EXTENDER;
+// Values from the Windows DDK headers, so the request layouts don't depend on the
+// `windows` crate.
+const FILE_DEVICE_BUSENUM: u32 = 0x0000_002A;
+const METHOD_BUFFERED: u32 = 0;
+const FILE_READ_ACCESS: u32 = 0x0001;
+const FILE_WRITE_ACCESS: u32 = 0x0002;
 const IOCTL_VIGEM_BASE: u32 = 0x801;
 
+/// The interface version this crate speaks, sent with `IOCTL_VIGEM_CHECK_VERSION`.
+pub(crate) const VIGEM_COMMON_VERSION: u32 = 0x0001;
+
 pub const IOCTL_VIGEM_PLUGIN_TARGET: u32 = ctl_code(
     FILE_DEVICE_BUSENUM,
     IOCTL_VIGEM_BASE,
@@ -89,6 +93,10 @@ pub(crate) struct CheckVersion {
     pub(crate) version: u32,
 }
 
+// Mirrors `VIGEM_PLUGIN_TARGET` from the ViGEmBus headers. The driver has no
+// extended plugin request, so VID/PID are the only device identity it accepts;
+// USB serial, manufacturer and product strings are generated by the driver itself.
+// There is no connection type either: DS4 targets always enumerate as USB devices.
 #[derive(Debug)]
 #[repr(C)]
 pub(crate) struct PluginTarget {
@@ -111,6 +119,8 @@ pub(crate) struct WaitDeviceReady {
     pub(crate) serial_no: u32,
 }
 
+// Mirrors `XUSB_REQUEST_NOTIFICATION`. Rumble and LED are the only host data the
+// driver fills in; there is no additional payload (e.g. audio) to surface.
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
 pub(crate) struct XusbRequestNotification {
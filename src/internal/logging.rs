@@ -0,0 +1,34 @@
+//! Logging macros that forward to the `log` crate when the `log` feature is enabled.
+//!
+//! Without the feature they only type-check their arguments, so values that are used
+//! for logging alone don't trigger unused warnings.
+
+macro_rules! trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+macro_rules! warn_ {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::warn!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+// `warn` is also the name of a built-in attribute, so it is renamed on export.
+pub(crate) use {debug, trace, warn_ as warn};
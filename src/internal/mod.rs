@@ -1,4 +1,10 @@
 pub(crate) mod bus;
+#[cfg(windows)]
+pub(crate) mod driver;
+// Most requests are only issued by the driver backend.
+#[cfg_attr(not(windows), allow(dead_code))]
 pub(crate) mod ioctl;
+pub(crate) mod logging;
 pub(crate) mod notification_workers;
+#[cfg(windows)]
 pub(crate) mod overlapped;
@@ -1,13 +1,225 @@
 #[cfg(feature = "ds4")]
-use crate::controller::ds4::{Ds4LightbarColor, Ds4Notification, Ds4OutputBuffer};
+use crate::controller::ds4::{Ds4FullOutput, Ds4LightbarColor, Ds4Notification, Ds4OutputBuffer};
 #[cfg(feature = "x360")]
-use crate::controller::x360::X360Notification;
+use crate::controller::x360::{X360Notification, X360NotificationBuffer};
+use crate::internal::bus::BusError;
 use crate::internal::ioctl::*;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, Weak};
+
+/// A destination for notifications produced by a notification thread.
+///
+/// The worker loop keeps running for as long as `send` succeeds. Once the
+/// receiving side is gone, `send` fails and the thread exits.
+pub(crate) trait NotificationSink<N>: Send + 'static {
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()>;
+}
+
+impl<N: Send + 'static> NotificationSink<N> for Sender<Result<N, BusError>> {
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        Sender::send(self, item).map_err(|_| ())
+    }
+}
+
+/// The receiving end of a [`drop_oldest_channel`], shared with its sender so that the
+/// sender can discard queued notifications.
+pub(crate) type SharedReceiver<N> = Mutex<Receiver<Result<N, BusError>>>;
+
+/// A bounded sink that drops the oldest queued notification when the buffer is full,
+/// so the worker loop never blocks on a slow consumer.
+pub(crate) struct DropOldestSender<N> {
+    sender: SyncSender<Result<N, BusError>>,
+    receiver: Weak<SharedReceiver<N>>,
+}
+
+/// Creates a bounded channel whose sender drops the oldest notification when full.
+///
+/// A `capacity` of zero is treated as one, since a rendezvous channel can't hold
+/// anything to drop.
+pub(crate) fn drop_oldest_channel<N>(
+    capacity: usize,
+) -> (DropOldestSender<N>, Arc<SharedReceiver<N>>) {
+    let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+    let receiver = Arc::new(Mutex::new(receiver));
+    let sender = DropOldestSender {
+        sender,
+        receiver: Arc::downgrade(&receiver),
+    };
+    (sender, receiver)
+}
+
+impl<N: Send + 'static> NotificationSink<N> for DropOldestSender<N> {
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        let mut item = item;
+        loop {
+            match self.sender.try_send(item) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(_)) => return Err(()),
+                Err(TrySendError::Full(rejected)) => {
+                    let Some(receiver) = self.receiver.upgrade() else {
+                        return Err(());
+                    };
+                    // Make room by discarding the oldest queued notification.
+                    match receiver.lock() {
+                        Ok(receiver) => {
+                            let _ = receiver.try_recv();
+                        }
+                        Err(_) => return Err(()),
+                    }
+                    item = rejected;
+                }
+            }
+        }
+    }
+}
+
+/// A sink that only keeps the most recent notification, for pull-based snapshots.
+///
+/// The worker thread stops once the snapshot owner is gone or a bus error occurs. The
+/// sink holds the only weak reference to the snapshot, so the owner can tell that the
+/// worker has stopped once there are no weak references left.
+pub(crate) struct SnapshotSink<N> {
+    latest: Weak<Mutex<Option<N>>>,
+}
+
+impl<N> SnapshotSink<N> {
+    pub(crate) fn new(latest: &Arc<Mutex<Option<N>>>) -> Self {
+        Self {
+            latest: Arc::downgrade(latest),
+        }
+    }
+}
+
+impl<N: Send + 'static> NotificationSink<N> for SnapshotSink<N> {
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        let latest = self.latest.upgrade().ok_or(())?;
+        let notification = item.map_err(|_| ())?;
+        *latest.lock().map_err(|_| ())? = Some(notification);
+        Ok(())
+    }
+}
+
+/// A sink that only forwards notifications that differ from the one it forwarded last.
+pub(crate) struct ChangedOnlySink<S, N> {
+    sink: S,
+    last: Mutex<Option<N>>,
+}
+
+impl<S, N> ChangedOnlySink<S, N> {
+    pub(crate) fn new(sink: S) -> Self {
+        Self {
+            sink,
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl<S, N> NotificationSink<N> for ChangedOnlySink<S, N>
+where
+    S: NotificationSink<N>,
+    N: Copy + PartialEq + Send + 'static,
+{
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        if let Ok(notification) = &item {
+            let mut last = self.last.lock().map_err(|_| ())?;
+            if *last == Some(*notification) {
+                return Ok(());
+            }
+            *last = Some(*notification);
+        }
+        self.sink.send(item)
+    }
+}
+
+pub(crate) type BoxedSink<N> = Box<dyn NotificationSink<N>>;
+
+/// The sinks attached to a running worker, so that every consumer of a target's
+/// notifications shares it instead of starting another one.
+///
+/// The driver completes each pending request with a single notification, so a second
+/// worker on the same target would take notifications away from the first one.
+pub(crate) struct WorkerTap<N> {
+    // `None` once the worker has stopped, so that nothing is attached to it anymore.
+    sinks: Mutex<Option<Vec<BoxedSink<N>>>>,
+}
+
+impl<N> WorkerTap<N> {
+    pub(crate) fn new(sink: BoxedSink<N>) -> Arc<Self> {
+        Arc::new(Self {
+            sinks: Mutex::new(Some(vec![sink])),
+        })
+    }
+
+    /// Attaches `sink` to the running worker, or hands it back if the worker has stopped.
+    pub(crate) fn attach(&self, sink: BoxedSink<N>) -> Result<(), BoxedSink<N>> {
+        match self.sinks.lock() {
+            Ok(mut sinks) => match sinks.as_mut() {
+                Some(sinks) => {
+                    sinks.push(sink);
+                    Ok(())
+                }
+                None => Err(sink),
+            },
+            Err(_) => Err(sink),
+        }
+    }
+}
+
+/// The sink of a shared worker, forwarding to every sink attached to its tap.
+///
+/// The worker keeps running for as long as any of them still receives. Sinks that fail
+/// are removed, and a bus error is only delivered to the first sink, as it can only be
+/// delivered once. The others are disconnected.
+pub(crate) struct TappedSink<N> {
+    tap: Arc<WorkerTap<N>>,
+}
+
+impl<N> TappedSink<N> {
+    pub(crate) fn new(tap: Arc<WorkerTap<N>>) -> Self {
+        Self { tap }
+    }
+}
+
+impl<N: Copy + Send + 'static> NotificationSink<N> for TappedSink<N> {
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        let mut sinks = self.tap.sinks.lock().map_err(|_| ())?;
+        let Some(attached) = sinks.as_mut() else {
+            return Err(());
+        };
+
+        match item {
+            Ok(notification) => attached.retain(|sink| sink.send(Ok(notification)).is_ok()),
+            Err(e) => {
+                if let Some(first) = attached.first() {
+                    let _ = first.send(Err(e));
+                }
+                attached.clear();
+            }
+        }
+
+        if attached.is_empty() {
+            *sinks = None;
+            return Err(());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<N: Send + 'static> NotificationSink<N>
+    for tokio::sync::mpsc::UnboundedSender<Result<N, BusError>>
+{
+    fn send(&self, item: Result<N, BusError>) -> Result<(), ()> {
+        tokio::sync::mpsc::UnboundedSender::send(self, item).map_err(|_| ())
+    }
+}
 
 pub(crate) trait NotificationWorker: Send + Sized + 'static {
-    type Notification: Send + 'static;
+    type Notification: Send + Copy + 'static;
     type Request: Default + Send + Copy + 'static;
     const IOCTL_CODE: u32;
+    /// Short label used to name the worker thread, e.g. `x360-notify`.
+    const THREAD_LABEL: &'static str;
 
     fn create_request(serial_no: u32) -> Self::Request;
     fn process_response(response: &Self::Request) -> Self::Notification;
@@ -22,6 +234,7 @@ impl NotificationWorker for X360NotificationWorker {
     type Request = XusbRequestNotification;
 
     const IOCTL_CODE: u32 = IOCTL_XUSB_REQUEST_NOTIFICATION;
+    const THREAD_LABEL: &'static str = "x360-notify";
 
     fn create_request(serial_no: u32) -> Self::Request {
         XusbRequestNotification {
@@ -42,6 +255,32 @@ impl NotificationWorker for X360NotificationWorker {
     }
 }
 
+#[cfg(feature = "x360")]
+pub(crate) struct X360RawNotificationWorker;
+
+#[cfg(feature = "x360")]
+impl NotificationWorker for X360RawNotificationWorker {
+    type Notification = X360NotificationBuffer;
+    // Issued as plain bytes, so every byte of the response is initialized and the
+    // buffer can be handed out as is.
+    type Request = [u8; size_of::<XusbRequestNotification>()];
+
+    const IOCTL_CODE: u32 = IOCTL_XUSB_REQUEST_NOTIFICATION;
+    const THREAD_LABEL: &'static str = "x360-notify-raw";
+
+    fn create_request(serial_no: u32) -> Self::Request {
+        let mut request = [0; size_of::<XusbRequestNotification>()];
+        let size = request.len() as u32;
+        request[0..4].copy_from_slice(&size.to_le_bytes());
+        request[4..8].copy_from_slice(&serial_no.to_le_bytes());
+        request
+    }
+
+    fn process_response(response: &Self::Request) -> Self::Notification {
+        X360NotificationBuffer { buf: *response }
+    }
+}
+
 #[cfg(feature = "ds4")]
 pub(crate) struct Ds4NotificationWorker;
 
@@ -51,6 +290,7 @@ impl NotificationWorker for Ds4NotificationWorker {
     type Request = Ds4RequestNotification;
 
     const IOCTL_CODE: u32 = IOCTL_DS4_REQUEST_NOTIFICATION;
+    const THREAD_LABEL: &'static str = "ds4-notify";
 
     fn create_request(serial_no: u32) -> Self::Request {
         Ds4RequestNotification {
@@ -82,6 +322,7 @@ impl NotificationWorker for Ds4OutputWorker {
     type Request = Ds4AwaitOutput;
 
     const IOCTL_CODE: u32 = IOCTL_DS4_AWAIT_OUTPUT_AVAILABLE;
+    const THREAD_LABEL: &'static str = "ds4-output";
 
     fn create_request(serial_no: u32) -> Self::Request {
         Ds4AwaitOutput {
@@ -97,3 +338,23 @@ impl NotificationWorker for Ds4OutputWorker {
         }
     }
 }
+
+#[cfg(feature = "ds4")]
+pub(crate) struct Ds4ParsedOutputWorker;
+
+#[cfg(feature = "ds4")]
+impl NotificationWorker for Ds4ParsedOutputWorker {
+    type Notification = Ds4FullOutput;
+    type Request = Ds4AwaitOutput;
+
+    const IOCTL_CODE: u32 = IOCTL_DS4_AWAIT_OUTPUT_AVAILABLE;
+    const THREAD_LABEL: &'static str = "ds4-output-parsed";
+
+    fn create_request(serial_no: u32) -> Self::Request {
+        Ds4OutputWorker::create_request(serial_no)
+    }
+
+    fn process_response(response: &Self::Request) -> Self::Notification {
+        Ds4FullOutput::from(&Ds4OutputWorker::process_response(response))
+    }
+}
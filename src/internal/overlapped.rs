@@ -3,7 +3,7 @@ use windows::Win32::{
     Foundation::CloseHandle,
     System::{
         IO::{GetOverlappedResult, OVERLAPPED},
-        Threading::CreateEventW,
+        Threading::{CreateEventW, ResetEvent},
     },
 };
 
@@ -26,6 +26,19 @@ impl OverlappedCall {
         }
     }
 
+    /// Prepares a completed call for reuse with another I/O request.
+    pub fn reset(&mut self) -> windows::core::Result<()> {
+        unsafe {
+            ResetEvent(self.inner.hEvent)?;
+        }
+        self.inner = OVERLAPPED {
+            hEvent: self.inner.hEvent,
+            ..Default::default()
+        };
+        self.transferred = 0;
+        Ok(())
+    }
+
     pub fn as_mut_overlapped(&mut self) -> *mut OVERLAPPED {
         &mut self.inner
     }
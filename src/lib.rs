@@ -1,13 +1,37 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(not(any(feature = "x360", feature = "ds4")))]
+compile_error!(
+    "vigem-rust needs at least one controller feature, enable `x360` and/or `ds4` in Cargo.toml"
+);
+
 mod internal;
 
 pub mod client;
 pub mod controller;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod target;
 
 pub use client::Client;
-pub use target::{TargetBuilder, TargetHandle};
+pub use controller::{Dpad8, Rumble};
+pub use target::{ControllerKind, TargetBuilder, TargetHandle};
+
+/// Checks whether the ViGEmBus driver is installed and its bus device is present.
+///
+/// This only enumerates the device interfaces and does not open the bus or perform a
+/// version check, making it a cheap probe for installers and diagnostics. A `true`
+/// result does not guarantee that [`Client::connect`] will succeed.
+///
+/// # Example
+/// ```no_run
+/// if !vigem_rust::is_bus_available() {
+///     eprintln!("ViGEmBus is not installed. Please install it first.");
+/// }
+/// ```
+pub fn is_bus_available() -> bool {
+    internal::bus::Bus::is_available()
+}
 
 #[cfg(feature = "x360")]
 pub use controller::x360::{X360Button, X360Notification, X360Report};
@@ -0,0 +1,520 @@
+//! An in-memory stand-in for the ViGEm bus, for testing code built on this crate.
+//!
+//! A [`Client`](crate::Client) created with [`Client::mock`](crate::Client::mock) never
+//! touches the driver. Plug, unplug and report calls are recorded in a [`MockBus`]
+//! instead, and notifications can be sent to the targets by hand. This lets input logic
+//! be unit tested on machines without ViGEmBus installed, such as CI runners, and on
+//! platforms other than Windows.
+//!
+//! Notifications go through the same notification threads as with the driver, so every
+//! way of registering for them works with the mock. Like the driver, the mock completes
+//! one pending request per notification, so two threads issuing the same kind of request
+//! for a target split its notifications between them.
+//!
+//! # Example
+//! ```
+//! use vigem_rust::{Client, X360Button, X360Notification, X360Report, mock::MockCall};
+//!
+//! let (client, bus) = Client::mock();
+//! let x360 = client.new_x360_target().plugin().unwrap();
+//! let notifications = x360.register_notification().unwrap();
+//!
+//! let mut report = X360Report::default();
+//! report.buttons.insert(X360Button::A);
+//! x360.update(&report).unwrap();
+//!
+//! assert!(matches!(
+//!     bus.calls().last(),
+//!     Some(MockCall::UpdateX360 { report, .. }) if report.buttons.contains(X360Button::A)
+//! ));
+//!
+//! // Targets are numbered from 1, like on a real bus.
+//! assert_eq!(bus.plugged(), [1]);
+//! let rumble = X360Notification { large_motor: 255, small_motor: 0, led_number: 0 };
+//! bus.send_x360_notification(1, rumble);
+//! assert_eq!(notifications.recv().unwrap().unwrap(), rumble);
+//! ```
+
+use std::{
+    any::Any,
+    collections::VecDeque,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+#[cfg(feature = "ds4")]
+use crate::controller::ds4::{Ds4Notification, Ds4Report, Ds4ReportEx};
+#[cfg(feature = "x360")]
+use crate::controller::x360::{X360Notification, X360Report};
+#[cfg(feature = "x360")]
+use crate::internal::ioctl::XusbRequestNotification;
+#[cfg(feature = "ds4")]
+use crate::internal::ioctl::{Ds4AwaitOutput, Ds4RequestNotification};
+use crate::{
+    internal::bus::{Backend, BusChild, BusError, NotificationRequest, NotificationSource},
+    internal::ioctl::VIGEM_COMMON_VERSION,
+    target::{ControllerKind, Target, TargetType},
+};
+
+/// A call made to a [`MockBus`], in the order it was received.
+///
+/// Only calls that succeeded are recorded.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum MockCall {
+    /// A target was plugged in.
+    Plug {
+        serial_no: u32,
+        kind: ControllerKind,
+        vendor_id: u16,
+        product_id: u16,
+    },
+    /// A target was unplugged.
+    Unplug { serial_no: u32 },
+    /// An Xbox 360 input report was submitted.
+    #[cfg(feature = "x360")]
+    UpdateX360 { serial_no: u32, report: X360Report },
+    /// A DualShock 4 input report was submitted.
+    #[cfg(feature = "ds4")]
+    UpdateDs4 { serial_no: u32, report: Ds4Report },
+    /// An extended DualShock 4 input report was submitted, as returned by
+    /// [`Ds4ReportEx::to_bytes`].
+    #[cfg(feature = "ds4")]
+    UpdateDs4Ex { serial_no: u32, bytes: [u8; 63] },
+}
+
+/// A notification sent with [`MockBus::send_x360_notification`] or
+/// [`MockBus::send_ds4_notification`].
+#[derive(Clone, Copy)]
+enum MockNotification {
+    #[cfg(feature = "x360")]
+    X360(X360Notification),
+    #[cfg(feature = "ds4")]
+    Ds4(Ds4Notification),
+}
+
+impl MockNotification {
+    /// Writes the notification into `request` the way the driver would, returning
+    /// `false` if the request is for another kind of notification.
+    fn fill(self, request: &mut dyn Any) -> bool {
+        match self {
+            #[cfg(feature = "x360")]
+            MockNotification::X360(notification) => {
+                if let Some(request) = request.downcast_mut::<XusbRequestNotification>() {
+                    request.large_motor = notification.large_motor;
+                    request.small_motor = notification.small_motor;
+                    request.led_number = notification.led_number;
+                } else if let Some(buf) =
+                    request.downcast_mut::<[u8; size_of::<XusbRequestNotification>()]>()
+                {
+                    buf[8] = notification.large_motor;
+                    buf[9] = notification.small_motor;
+                    buf[10] = notification.led_number;
+                } else {
+                    return false;
+                }
+            }
+            #[cfg(feature = "ds4")]
+            MockNotification::Ds4(notification) => {
+                if let Some(request) = request.downcast_mut::<Ds4RequestNotification>() {
+                    request.report.large_motor = notification.large_motor;
+                    request.report.small_motor = notification.small_motor;
+                    request.report.lightbar_color.red = notification.lightbar.red;
+                    request.report.lightbar_color.green = notification.lightbar.green;
+                    request.report.lightbar_color.blue = notification.lightbar.blue;
+                } else if let Some(request) = request.downcast_mut::<Ds4AwaitOutput>() {
+                    // A USB output report (ID 0x05) as the host would write it.
+                    let buf = &mut request.report.buffer;
+                    buf[0] = 0x05;
+                    buf[4] = notification.small_motor;
+                    buf[5] = notification.large_motor;
+                    buf[6] = notification.lightbar.red;
+                    buf[7] = notification.lightbar.green;
+                    buf[8] = notification.lightbar.blue;
+                } else {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+struct PluggedTarget {
+    serial_no: u32,
+    kind: TargetType,
+    vendor_id: u16,
+    product_id: u16,
+    // Tells a replugged target apart from the one a notification source was opened for.
+    plug_id: u64,
+    // One queue per kind of notification request issued for the target.
+    queues: Vec<NotificationQueue>,
+}
+
+impl PluggedTarget {
+    fn queue(&mut self, ioctl_code: u32) -> Option<&mut NotificationQueue> {
+        self.queues
+            .iter_mut()
+            .find(|queue| queue.ioctl_code == ioctl_code)
+    }
+}
+
+/// The notifications waiting for a request of one kind. Each of them completes a single
+/// request, like with the driver.
+struct NotificationQueue {
+    ioctl_code: u32,
+    // The number of open sources issuing requests of this kind. Notifications are only
+    // queued while there is at least one.
+    sources: usize,
+    pending: VecDeque<MockNotification>,
+}
+
+#[derive(Default)]
+struct MockState {
+    calls: Vec<MockCall>,
+    // In the order they were plugged in.
+    plugged: Vec<PluggedTarget>,
+    next_plug_id: u64,
+}
+
+impl MockState {
+    fn target(&self, serial_no: u32) -> Result<&PluggedTarget, BusError> {
+        self.plugged
+            .iter()
+            .find(|target| target.serial_no == serial_no)
+            .ok_or(BusError::TargetNotFound(serial_no))
+    }
+
+    /// Returns the target a notification source was opened for, unless it has been
+    /// unplugged or replugged since.
+    fn source_target(&mut self, serial_no: u32, plug_id: u64) -> Option<&mut PluggedTarget> {
+        self.plugged
+            .iter_mut()
+            .find(|target| target.serial_no == serial_no && target.plug_id == plug_id)
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<MockState>,
+    // Signaled whenever a notification is sent or a target is unplugged.
+    changed: Condvar,
+}
+
+/// The recording side of a mocked bus, shared with the [`Client`](crate::Client) it
+/// was created with.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Default)]
+pub struct MockBus {
+    shared: Arc<Shared>,
+}
+
+impl MockBus {
+    /// Returns all calls recorded so far.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state().calls.clone()
+    }
+
+    /// Returns all calls recorded so far and clears the record.
+    pub fn take_calls(&self) -> Vec<MockCall> {
+        std::mem::take(&mut self.state().calls)
+    }
+
+    /// Returns the serial numbers of the plugged in targets, in the order they were
+    /// plugged in.
+    pub fn plugged(&self) -> Vec<u32> {
+        self.state()
+            .plugged
+            .iter()
+            .map(|target| target.serial_no)
+            .collect()
+    }
+
+    /// Returns whether a target with the given serial number is plugged in.
+    pub fn is_plugged(&self, serial_no: u32) -> bool {
+        self.state().target(serial_no).is_ok()
+    }
+
+    /// Sends a notification to the Xbox 360 target with the given serial number, as if
+    /// the host had sent it.
+    ///
+    /// The notification completes a single pending notification request of the target,
+    /// so every receiver fed by that request's thread gets it. Receivers registered after
+    /// this call don't get it. If no such target is plugged in, the notification is
+    /// dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use vigem_rust::{Client, X360Notification};
+    ///
+    /// let (client, bus) = Client::mock();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    /// let raw = x360.register_notification_raw_buffer().unwrap();
+    ///
+    /// let rumble = X360Notification { large_motor: 10, small_motor: 20, led_number: 1 };
+    /// bus.send_x360_notification(1, rumble);
+    /// assert_eq!(raw.recv().unwrap().unwrap().notification(), rumble);
+    /// ```
+    #[cfg(feature = "x360")]
+    pub fn send_x360_notification(&self, serial_no: u32, notification: X360Notification) {
+        self.notify(serial_no, MockNotification::X360(notification));
+    }
+
+    /// Sends a notification to the DualShock 4 target with the given serial number, as if
+    /// the host had sent it.
+    ///
+    /// The notification completes a single pending notification request and a single
+    /// pending output request of the target, so every receiver fed by those requests'
+    /// threads gets it. Output receivers get it as a USB output report. Receivers
+    /// registered after this call don't get it. If no such target is plugged in, the
+    /// notification is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use vigem_rust::{Client, Ds4LightbarColor, Ds4Notification};
+    ///
+    /// let (client, bus) = Client::mock();
+    /// let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// let output = ds4.register_notification_parsed().unwrap();
+    ///
+    /// let lightbar = Ds4LightbarColor::new(255, 0, 64);
+    /// bus.send_ds4_notification(
+    ///     1,
+    ///     Ds4Notification { large_motor: 200, small_motor: 0, lightbar },
+    /// );
+    /// let output = output.recv().unwrap().unwrap();
+    /// assert_eq!(output.report_id, 0x05);
+    /// assert_eq!(output.rumble.large, 200);
+    /// assert_eq!(output.lightbar, lightbar);
+    /// ```
+    #[cfg(feature = "ds4")]
+    pub fn send_ds4_notification(&self, serial_no: u32, notification: Ds4Notification) {
+        self.notify(serial_no, MockNotification::Ds4(notification));
+    }
+
+    fn notify(&self, serial_no: u32, notification: MockNotification) {
+        let mut state = self.state();
+        if let Some(target) = state
+            .plugged
+            .iter_mut()
+            .find(|target| target.serial_no == serial_no)
+        {
+            for queue in &mut target.queues {
+                if queue.sources > 0 {
+                    queue.pending.push_back(notification);
+                }
+            }
+            self.shared.changed.notify_all();
+        }
+    }
+
+    /// Records `call` if the target with the given serial number is plugged in.
+    fn submit(&self, serial_no: u32, call: MockCall) -> Result<(), BusError> {
+        let mut state = self.state();
+        state.target(serial_no)?;
+        state.calls.push(call);
+        Ok(())
+    }
+
+    #[inline]
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        // The state is only ever pushed to or removed from, so it is usable after a panic.
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Backend for MockBus {
+    fn device_path(&self) -> &str {
+        "mock"
+    }
+
+    fn driver_version(&self) -> Result<u32, BusError> {
+        Ok(VIGEM_COMMON_VERSION)
+    }
+
+    fn enumerate_children(&self) -> Result<Vec<BusChild>, BusError> {
+        Ok(self
+            .state()
+            .plugged
+            .iter()
+            .map(|target| BusChild {
+                serial_no: target.serial_no,
+                kind: Some(target.kind),
+                vendor_id: target.vendor_id,
+                product_id: target.product_id,
+            })
+            .collect())
+    }
+
+    fn plug(&self, target: &Target, serial_no: u32) -> Result<(), BusError> {
+        let mut state = self.state();
+        // Like the driver, refuse serial numbers that are already in use.
+        if state.target(serial_no).is_ok() {
+            return Err(BusError::SerialInUse(serial_no));
+        }
+
+        let plug_id = state.next_plug_id;
+        state.next_plug_id += 1;
+        state.plugged.push(PluggedTarget {
+            serial_no,
+            kind: target.kind,
+            vendor_id: target.vendor_id,
+            product_id: target.product_id,
+            plug_id,
+            queues: Vec::new(),
+        });
+        state.calls.push(MockCall::Plug {
+            serial_no,
+            kind: target.kind.into(),
+            vendor_id: target.vendor_id,
+            product_id: target.product_id,
+        });
+        Ok(())
+    }
+
+    fn unplug(&self, serial_no: u32) -> Result<(), BusError> {
+        let mut state = self.state();
+        let index = state
+            .plugged
+            .iter()
+            .position(|target| target.serial_no == serial_no)
+            .ok_or(BusError::TargetNotFound(serial_no))?;
+
+        state.plugged.remove(index);
+        state.calls.push(MockCall::Unplug { serial_no });
+        // Pending notification requests fail once their target is gone, like with the driver.
+        self.shared.changed.notify_all();
+        Ok(())
+    }
+
+    #[cfg(feature = "x360")]
+    fn update_x360(&self, serial_no: u32, report: &X360Report) -> Result<(), BusError> {
+        self.submit(
+            serial_no,
+            MockCall::UpdateX360 {
+                serial_no,
+                report: *report,
+            },
+        )
+    }
+
+    /// Returns the user index of a plugged in Xbox 360 target, which the mock assigns
+    /// in the order the Xbox 360 targets were plugged in.
+    #[cfg(feature = "x360")]
+    fn x360_user_index(&self, serial_no: u32) -> Result<u32, BusError> {
+        self.state()
+            .plugged
+            .iter()
+            .filter(|target| target.kind == TargetType::Xbox360)
+            .position(|target| target.serial_no == serial_no)
+            .map(|index| index as u32)
+            .ok_or(BusError::TargetNotFound(serial_no))
+    }
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4(&self, serial_no: u32, report: &Ds4Report) -> Result<(), BusError> {
+        self.submit(
+            serial_no,
+            MockCall::UpdateDs4 {
+                serial_no,
+                report: *report,
+            },
+        )
+    }
+
+    #[cfg(feature = "ds4")]
+    fn update_ds4_ex(&self, serial_no: u32, report: &Ds4ReportEx) -> Result<(), BusError> {
+        self.submit(
+            serial_no,
+            MockCall::UpdateDs4Ex {
+                serial_no,
+                bytes: report.to_bytes(),
+            },
+        )
+    }
+
+    fn open_notifications(
+        self: Arc<Self>,
+        serial_no: u32,
+        ioctl_code: u32,
+    ) -> Result<Box<dyn NotificationSource>, BusError> {
+        let plug_id = {
+            let mut state = self.state();
+            let target = state
+                .plugged
+                .iter_mut()
+                .find(|target| target.serial_no == serial_no)
+                .ok_or(BusError::TargetNotFound(serial_no))?;
+
+            match target.queue(ioctl_code) {
+                Some(queue) => queue.sources += 1,
+                None => target.queues.push(NotificationQueue {
+                    ioctl_code,
+                    sources: 1,
+                    pending: VecDeque::new(),
+                }),
+            }
+            target.plug_id
+        };
+
+        Ok(Box::new(MockNotifications {
+            bus: MockBus::clone(&self),
+            serial_no,
+            plug_id,
+            ioctl_code,
+        }))
+    }
+}
+
+/// Issues notification requests of one kind for a target, each completed by the next
+/// notification sent to it that no other request has taken.
+struct MockNotifications {
+    bus: MockBus,
+    serial_no: u32,
+    plug_id: u64,
+    ioctl_code: u32,
+}
+
+impl NotificationSource for MockNotifications {
+    fn next(&mut self, request: &mut dyn NotificationRequest) -> Result<(), BusError> {
+        let mut state = self.bus.state();
+        loop {
+            let queue = state
+                .source_target(self.serial_no, self.plug_id)
+                .and_then(|target| target.queue(self.ioctl_code))
+                .ok_or(BusError::TargetNotFound(self.serial_no))?;
+
+            while let Some(notification) = queue.pending.pop_front() {
+                if notification.fill(request.as_any_mut()) {
+                    return Ok(());
+                }
+            }
+
+            state = self
+                .bus
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl Drop for MockNotifications {
+    fn drop(&mut self) {
+        let mut state = self.bus.state();
+        if let Some(queue) = state
+            .source_target(self.serial_no, self.plug_id)
+            .and_then(|target| target.queue(self.ioctl_code))
+        {
+            queue.sources -= 1;
+            // Nothing is waiting for these anymore.
+            if queue.sources == 0 {
+                queue.pending.clear();
+            }
+        }
+    }
+}
@@ -1,24 +1,40 @@
 use std::{
     marker::PhantomData,
+    ops::Deref,
     sync::{
-        Arc, Mutex, Weak,
-        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc, Mutex, MutexGuard, PoisonError, Weak,
+        atomic::{AtomicU32, Ordering},
+        mpsc::{self, Receiver, RecvError, RecvTimeoutError, TryRecvError},
     },
-    time::Duration,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
 #[cfg(feature = "ds4")]
-use crate::controller::ds4::{Ds4Notification, Ds4OutputBuffer, Ds4Report, Ds4ReportEx};
+use crate::controller::ds4::{
+    Ds4FullOutput, Ds4Notification, Ds4OutputBuffer, Ds4Report, Ds4ReportEx,
+};
 #[cfg(feature = "x360")]
-use crate::controller::x360::{X360Notification, X360Report};
+use crate::controller::x360::{X360Notification, X360NotificationBuffer, X360Report};
 
 use crate::{
     client::{Client, ClientError, ClientInner},
-    internal::bus::{Bus, BusError},
+    internal::{
+        bus::{Bus, BusError},
+        notification_workers::{
+            ChangedOnlySink, NotificationSink, SharedReceiver, SnapshotSink, TappedSink, WorkerTap,
+            drop_oldest_channel,
+        },
+    },
 };
 
+#[cfg(feature = "tokio")]
+use tokio_stream::{Stream, wrappers::UnboundedReceiverStream};
+
 #[repr(u32)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// With one controller feature, the other kind is only read back from the bus on Windows.
+#[cfg_attr(not(windows), allow(dead_code))]
 pub(crate) enum TargetType {
     Xbox360 = 0,
     DualShock4 = 2,
@@ -35,12 +51,35 @@ impl TargetType {
     }
 }
 
-#[derive(Debug)]
+/// The type of a virtual controller, available at runtime.
+///
+/// Returned by `controller_kind` on [`TargetHandle`]s, which is useful when handles of
+/// different controller types are stored behind a common enum or trait object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControllerKind {
+    Xbox360,
+    DualShock4,
+}
+
+impl From<TargetType> for ControllerKind {
+    fn from(kind: TargetType) -> Self {
+        match kind {
+            TargetType::Xbox360 => ControllerKind::Xbox360,
+            TargetType::DualShock4 => ControllerKind::DualShock4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub(crate) struct Target {
+    #[cfg_attr(not(windows), allow(dead_code))]
     pub(crate) kind: TargetType,
     pub(crate) serial_no: u32,
     pub(crate) vendor_id: u16,
     pub(crate) product_id: u16,
+    // Skips `IOCTL_VIGEM_WAIT_DEVICE_READY` when plugging in.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) skip_driver_wait: bool,
 }
 
 #[cfg(feature = "x360")]
@@ -53,20 +92,102 @@ pub struct Xbox360;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DualShock4;
 
-struct TargetHandleInner<T> {
-    serial_no: u32,
+mod private {
+    pub trait Sealed {}
+}
+
+/// Implemented by the controller marker types, such as [`Xbox360`] and [`DualShock4`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait TargetKind: private::Sealed + Send + Sync + 'static {
+    /// The standard input report submitted to this kind of controller.
+    type Report: Default + Copy + Send;
+    /// The notification received from the bus for this kind of controller.
+    type Notification: Copy + Send + 'static;
+}
+
+#[cfg(feature = "x360")]
+impl private::Sealed for Xbox360 {}
+
+#[cfg(feature = "x360")]
+impl TargetKind for Xbox360 {
+    type Report = X360Report;
+    type Notification = X360Notification;
+}
+
+#[cfg(feature = "ds4")]
+impl private::Sealed for DualShock4 {}
+
+#[cfg(feature = "ds4")]
+impl TargetKind for DualShock4 {
+    type Report = Ds4Report;
+    type Notification = Ds4Notification;
+}
+
+/// Starts the notification worker that all consumers of a target's notifications share.
+pub(crate) trait SharedNotifications: TargetKind {
+    fn start_notification_worker(
+        bus: &Bus,
+        serial_no: u32,
+        sink: TappedSink<Self::Notification>,
+    ) -> Result<(), BusError>;
+}
+
+#[cfg(feature = "x360")]
+impl SharedNotifications for Xbox360 {
+    fn start_notification_worker(
+        bus: &Bus,
+        serial_no: u32,
+        sink: TappedSink<X360Notification>,
+    ) -> Result<(), BusError> {
+        bus.start_x360_notification_thread(serial_no, sink)
+    }
+}
+
+#[cfg(feature = "ds4")]
+impl SharedNotifications for DualShock4 {
+    fn start_notification_worker(
+        bus: &Bus,
+        serial_no: u32,
+        sink: TappedSink<Ds4Notification>,
+    ) -> Result<(), BusError> {
+        bus.start_ds4_notification_thread(serial_no, sink)
+    }
+}
+
+type NotificationSnapshot<N> = Arc<Mutex<Option<N>>>;
+
+struct TargetHandleInner<T: TargetKind> {
+    // Atomic because `replug` may move the target to a different serial.
+    serial_no: AtomicU32,
+    // Every handle owns a reference to the bus, so the driver handle stays open until
+    // the last handle and the client are gone, whichever of them drops last.
     bus: Bus,
     client_inner: Weak<Mutex<ClientInner>>,
+    // The last report submitted through `update`, used as the base for `update_with`.
+    last_report: Mutex<T::Report>,
+    // Lazily started on the first `last_notification` call.
+    notification_snapshot: Mutex<Option<NotificationSnapshot<T::Notification>>>,
+    // The sinks of the running notification worker, shared by every consumer of the
+    // target's notifications.
+    worker_tap: Mutex<Weak<WorkerTap<T::Notification>>>,
+    // When a report was last submitted successfully, used by `update_throttled`.
+    last_submit: Mutex<Option<Instant>>,
     _marker: PhantomData<T>,
 }
 
-impl<T> Drop for TargetHandleInner<T> {
+impl<T: TargetKind> Drop for TargetHandleInner<T> {
     fn drop(&mut self) {
-        if let Some(inner_arc) = self.client_inner.upgrade()
-            && let Ok(mut inner) = inner_arc.lock()
-            && inner.targets.remove(&self.serial_no).is_some()
-        {
-            let _ = self.bus.unplug(self.serial_no);
+        // If the client is already gone, its own drop has unplugged the target. Otherwise
+        // the client lock decides the race: whoever removes the serial from `targets`
+        // first unplugs it, so a target is never unplugged twice. A poisoned lock is
+        // still used, since skipping the unplug would leak the virtual controller.
+        let serial_no = *self.serial_no.get_mut();
+        if let Some(inner_arc) = self.client_inner.upgrade() {
+            let mut inner = inner_arc.lock().unwrap_or_else(PoisonError::into_inner);
+            if inner.targets.remove(&serial_no).is_some() {
+                inner.unplug_reporting(serial_no);
+            }
         }
     }
 }
@@ -81,30 +202,194 @@ impl<T> Drop for TargetHandleInner<T> {
 /// handle to the same virtual controller. The controller is only unplugged from the bus
 /// when the **last** handle is dropped.
 #[derive(Clone)]
-pub struct TargetHandle<T> {
+pub struct TargetHandle<T: TargetKind> {
     inner: Arc<TargetHandleInner<T>>,
 }
 
-impl<T> TargetHandle<T> {
+impl<T: TargetKind> TargetHandle<T> {
     pub(crate) fn new(serial_no: u32, bus: Bus, client_inner: Weak<Mutex<ClientInner>>) -> Self {
         Self {
             inner: Arc::new(TargetHandleInner {
-                serial_no,
+                serial_no: AtomicU32::new(serial_no),
                 bus,
                 client_inner,
+                last_report: Mutex::new(T::Report::default()),
+                notification_snapshot: Mutex::new(None),
+                worker_tap: Mutex::new(Weak::new()),
+                last_submit: Mutex::new(None),
                 _marker: PhantomData,
             }),
         }
     }
 
+    #[inline]
+    fn serial_no(&self) -> u32 {
+        self.inner.serial_no.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn last_report(&self) -> Result<MutexGuard<'_, T::Report>, ClientError> {
+        self.inner
+            .last_report
+            .lock()
+            .map_err(|_| ClientError::Poisoned)
+    }
+
+    /// Returns the latest notification, attaching the snapshot to the notification worker
+    /// on first use.
+    fn last_notification_with(&self) -> Result<Option<T::Notification>, ClientError>
+    where
+        T: SharedNotifications,
+    {
+        let mut snapshot = self
+            .inner
+            .notification_snapshot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        let latest = match &*snapshot {
+            // The worker holds the only weak reference, so it is still running.
+            Some(latest) if Arc::weak_count(latest) > 0 => latest.clone(),
+            // Not started yet, or stopped by a bus error. A restarted worker keeps the
+            // last notification until a new one arrives.
+            previous => {
+                let last = previous
+                    .as_ref()
+                    .and_then(|previous| *previous.lock().unwrap_or_else(PoisonError::into_inner));
+                let latest = Arc::new(Mutex::new(last));
+                self.subscribe(SnapshotSink::new(&latest))?;
+                snapshot.insert(latest).clone()
+            }
+        };
+
+        let notification = *latest.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(notification)
+    }
+
+    /// Attaches `sink` to the notification worker of this target, or starts the worker
+    /// if none is running.
+    ///
+    /// The driver completes each pending request with a single notification, so all
+    /// consumers of a target share one worker to each see every notification.
+    fn subscribe<S>(&self, sink: S) -> Result<(), ClientError>
+    where
+        T: SharedNotifications,
+        S: NotificationSink<T::Notification>,
+    {
+        // Held until the worker is started, so concurrent calls can't both start one.
+        let mut worker_tap = self.worker_tap();
+        let sink = match worker_tap.upgrade() {
+            Some(tap) => match tap.attach(Box::new(sink)) {
+                Ok(()) => return Ok(()),
+                // The worker is stopping, e.g. after a bus error. Start a new one.
+                Err(sink) => sink,
+            },
+            None => Box::new(sink),
+        };
+
+        let tap = WorkerTap::new(sink);
+        T::start_notification_worker(
+            &self.inner.bus,
+            self.serial_no(),
+            TappedSink::new(tap.clone()),
+        )?;
+        *worker_tap = Arc::downgrade(&tap);
+        Ok(())
+    }
+
+    /// Returns a receiver fed by the notification worker of this target.
+    fn register_shared(&self) -> Result<Receiver<Result<T::Notification, BusError>>, ClientError>
+    where
+        T: SharedNotifications,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribe(sender)?;
+        Ok(receiver)
+    }
+
+    #[inline]
+    fn worker_tap(&self) -> MutexGuard<'_, Weak<WorkerTap<T::Notification>>> {
+        self.inner
+            .worker_tap
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Waits until the target is ready, watching the notifications of the shared worker.
+    fn wait_for_ready_with(&self) -> Result<ReadyOutcome, ClientError>
+    where
+        T: SharedNotifications,
+    {
+        let receiver = self.register_shared()?;
+        wait_for_notifications_internal(receiver, self.serial_no())
+    }
+
+    /// Spawns a thread that calls `f` for every notification received through `receiver`.
+    fn spawn_listener<F>(
+        &self,
+        label: &str,
+        receiver: Receiver<Result<T::Notification, BusError>>,
+        mut f: F,
+    ) -> Result<JoinHandle<()>, ClientError>
+    where
+        F: FnMut(T::Notification) + Send + 'static,
+    {
+        let handle = thread::Builder::new()
+            .name(format!("vigem-{label}-listener-{}", self.serial_no()))
+            .spawn(move || {
+                while let Ok(Ok(notification)) = receiver.recv() {
+                    f(notification);
+                }
+            })
+            .map_err(BusError::from)?;
+        Ok(handle)
+    }
+
+    /// Moves this handle onto a background thread that submits the reports of the
+    /// returned [`ReportSender`] through `update`.
+    fn into_sender_with(
+        self,
+        label: &str,
+        update: fn(&Self, &T::Report) -> Result<(), ClientError>,
+    ) -> Result<ReportSender<T>, ClientError> {
+        let serial_no = self.serial_no();
+        let (sender, receiver) = mpsc::channel::<T::Report>();
+        let error = Arc::new(Mutex::new(None));
+        let worker_error = error.clone();
+
+        thread::Builder::new()
+            .name(format!("vigem-{label}-sender-{serial_no}"))
+            .spawn(move || {
+                for report in receiver {
+                    if let Err(e) = update(&self, &report) {
+                        let gone = matches!(
+                            e,
+                            ClientError::TargetDoesNotExist(_) | ClientError::ClientNoLongerExists
+                        );
+                        *worker_error.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+                        if gone {
+                            break;
+                        }
+                    }
+                }
+            })
+            .map_err(BusError::from)?;
+
+        Ok(ReportSender {
+            sender,
+            error,
+            serial_no,
+        })
+    }
+
     fn with_client<F, R>(&self, f: F) -> Result<R, ClientError>
     where
         F: FnOnce(&ClientInner) -> Result<R, ClientError>,
     {
         if let Some(inner_arc) = self.inner.client_inner.upgrade() {
-            let inner = inner_arc.lock().expect("Client mutex was poisoned");
-            if !inner.targets.contains_key(&self.inner.serial_no) {
-                return Err(ClientError::TargetDoesNotExist(self.inner.serial_no));
+            let inner = inner_arc.lock().map_err(|_| ClientError::Poisoned)?;
+            if !inner.targets.contains_key(&self.serial_no()) {
+                return Err(ClientError::TargetDoesNotExist(self.serial_no()));
             }
             f(&inner)
         } else {
@@ -112,12 +397,154 @@ impl<T> TargetHandle<T> {
         }
     }
 
+    /// Submits a report through `f`, but only while the target is still plugged in.
+    ///
+    /// The client lock is held across the check and the request, so a concurrent
+    /// `unplug` or `replug` can't move the serial to another target in between.
+    fn submit<F>(&self, f: F) -> Result<(), ClientError>
+    where
+        F: FnOnce(&Bus, u32) -> Result<(), BusError>,
+    {
+        self.submit_holding(None, f)
+    }
+
+    /// Like [`submit`](Self::submit), but records the submission time in `last_submit`
+    /// when the caller already holds that lock.
+    fn submit_holding<F>(
+        &self,
+        last_submit: Option<&mut Option<Instant>>,
+        f: F,
+    ) -> Result<(), ClientError>
+    where
+        F: FnOnce(&Bus, u32) -> Result<(), BusError>,
+    {
+        self.with_client(|inner| Ok(f(&inner.bus, self.serial_no())?))?;
+
+        let now = Some(Instant::now());
+        match last_submit {
+            Some(last_submit) => *last_submit = now,
+            None => *self.last_submit() = now,
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn last_submit(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.inner
+            .last_submit
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `submit` unless the last successful submission is less than `min_interval` ago.
+    ///
+    /// The submission time stays locked until `submit` returns, so concurrent callers
+    /// can't both pass the check. `submit` records the new time through the value it is
+    /// given.
+    fn throttled<F>(&self, min_interval: Duration, submit: F) -> Result<(), ClientError>
+    where
+        F: FnOnce(&mut Option<Instant>) -> Result<(), ClientError>,
+    {
+        let mut last_submit = self.last_submit();
+        if last_submit.is_some_and(|last| last.elapsed() < min_interval) {
+            return Ok(());
+        }
+        submit(&mut last_submit)
+    }
+
+    /// Discards every notification that is already queued in `receiver`.
+    ///
+    /// Right after plugging in and `wait_for_ready`, the host may still be flushing
+    /// enumeration-time notifications into receivers registered earlier. Draining such a
+    /// receiver lets consumers react only to feedback sent from now on.
+    ///
+    /// # Errors
+    /// If one of the discarded items is a bus error, that error is returned instead.
+    /// Returns [`ClientError::TargetDoesNotExist`] if the notification thread feeding
+    /// `receiver` has stopped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    /// let receiver = x360.register_notification()?;
+    /// x360.wait_for_ready()?;
+    ///
+    /// x360.drain_notifications(&receiver)?;
+    /// while let Ok(Ok(notification)) = receiver.recv() {
+    ///     println!("{notification:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drain_notifications(
+        &self,
+        receiver: &Receiver<Result<T::Notification, BusError>>,
+    ) -> Result<(), ClientError> {
+        loop {
+            match receiver.try_recv() {
+                Ok(Ok(_)) => continue,
+                Ok(Err(bus_error)) => return Err(bus_error.into()),
+                Err(TryRecvError::Empty) => return Ok(()),
+                Err(TryRecvError::Disconnected) => {
+                    return Err(ClientError::TargetDoesNotExist(self.serial_no()));
+                }
+            }
+        }
+    }
+
     /// Checks if the virtual controller is still attached to the bus.
     ///
     /// This can return `false` if the controller was manually unplugged
     /// or if the client was dropped.
     pub fn is_attached(&self) -> Result<bool, ClientError> {
-        self.with_client(|inner| Ok(inner.targets.contains_key(&self.inner.serial_no)))
+        self.with_client(|inner| Ok(inner.targets.contains_key(&self.serial_no())))
+    }
+
+    /// Calls `f` on a report `hz` times per second and submits the result through `submit`,
+    /// until `f` returns `false`.
+    fn run_at_hz_with<F, S>(&self, hz: u32, mut f: F, submit: S) -> Result<(), ClientError>
+    where
+        F: FnMut(&mut T::Report) -> bool,
+        S: Fn(&T::Report) -> Result<(), ClientError>,
+    {
+        let period = Duration::from_secs(1) / hz.max(1);
+        let mut report = T::Report::default();
+        let mut next_tick = Instant::now();
+
+        while f(&mut report) {
+            submit(&report)?;
+
+            next_tick += period;
+            let now = Instant::now();
+            if next_tick > now {
+                thread::sleep(next_tick - now);
+            } else if now - next_tick > period {
+                // Fell behind by more than a tick, e.g. after a stall. Continue from now
+                // instead of submitting a burst of reports to catch up.
+                next_tick = now;
+            }
+        }
+        Ok(())
+    }
+
+    /// Asks the driver about the target through `probe`, which must not change any of its
+    /// state, and reports whether the driver still has it.
+    fn ping_with<F>(&self, probe: F) -> Result<bool, ClientError>
+    where
+        F: FnOnce(&Bus, u32) -> Result<(), BusError>,
+    {
+        let result = self
+            .with_client(|_| Ok(()))
+            .and_then(|()| Ok(probe(&self.inner.bus, self.serial_no())?));
+        match result {
+            Ok(()) => Ok(true),
+            Err(ClientError::TargetDoesNotExist(_)) => Ok(false),
+            Err(ClientError::BusError(e)) if !e.is_transient() => Ok(false),
+            Err(e) => Err(e),
+        }
     }
 
     /// Explicitly unplugs the virtual controller from the bus.
@@ -127,19 +554,166 @@ impl<T> TargetHandle<T> {
     /// the last [`TargetHandle`] is dropped.
     pub fn unplug(&self) -> Result<(), ClientError> {
         if let Some(inner_arc) = self.inner.client_inner.upgrade() {
-            let mut inner = inner_arc.lock().expect("Client mutex was poisoned");
-            if inner.targets.remove(&self.inner.serial_no).is_some() {
-                let _ = self.inner.bus.unplug(self.inner.serial_no);
+            let mut inner = inner_arc.lock().map_err(|_| ClientError::Poisoned)?;
+            if inner.targets.remove(&self.serial_no()).is_some() {
+                inner.unplug_reporting(self.serial_no());
             }
             Ok(())
         } else {
             Err(ClientError::ClientNoLongerExists)
         }
     }
+
+    /// Wraps the handle in a guard that unplugs the controller when it goes out of scope.
+    ///
+    /// Normally the controller stays plugged in until the last clone of the handle is
+    /// dropped. A [`ScopedTarget`] unplugs it on drop regardless of other clones, so a
+    /// stray clone can't keep a temporary controller alive. Those clones then fail like
+    /// after an explicit [`unplug`](Self::unplug).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Report};
+    /// # let client = Client::connect().unwrap();
+    /// let x360 = client.new_x360_target().plugin().unwrap().scoped();
+    /// let leaked = x360.clone();
+    ///
+    /// {
+    ///     let x360 = x360;
+    ///     x360.wait_for_ready().unwrap();
+    ///     x360.update(&X360Report::default()).unwrap();
+    /// } // Unplugged here.
+    ///
+    /// assert!(leaked.update(&X360Report::default()).is_err());
+    /// ```
+    pub fn scoped(self) -> ScopedTarget<T> {
+        ScopedTarget { handle: self }
+    }
+
+    /// Unplugs the virtual controller and immediately plugs it back in.
+    ///
+    /// This simulates a physical disconnect/reconnect cycle, e.g. for testing how a game
+    /// handles hot-unplugging. The target is re-plugged with the same type, VID and PID,
+    /// but the bus may assign it a different serial number. This handle and all of its
+    /// clones stay valid and refer to the re-plugged controller afterwards.
+    ///
+    /// Notification receivers registered before the call keep listening to the old
+    /// device and stop receiving notifications, so register them again if needed.
+    /// As with a freshly plugged target, call `wait_for_ready` before sending updates.
+    ///
+    /// If plugging the target back in fails, it stays unplugged and further operations
+    /// on this handle return [`ClientError::TargetDoesNotExist`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    /// x360.wait_for_ready()?;
+    ///
+    /// x360.replug()?;
+    /// x360.wait_for_ready()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replug(&self) -> Result<(), ClientError> {
+        self.replug_internal(None)
+    }
+
+    /// Replaces the Vendor ID and Product ID of the plugged-in virtual controller.
+    ///
+    /// The ViGEm bus fixes the identifiers at plug time, so this unplugs the target and
+    /// plugs it back in with `vid` and `pid`. It stays on the same serial number if that
+    /// slot can be reused, otherwise the bus assigns a new one. Like
+    /// [`replug`](Self::replug), this handle and all of its clones stay valid, but
+    /// notification receivers have to be registered again and `wait_for_ready` should be
+    /// called before sending updates.
+    ///
+    /// # Errors
+    /// Returns [`ClientError::InvalidIdentifiers`] without touching the target if `vid` or
+    /// `pid` is zero. If plugging the target back in fails, it stays unplugged.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    ///
+    /// // The game turned out to only accept a different device signature.
+    /// x360.change_identifiers(0x045E, 0x02A1)?;
+    /// x360.wait_for_ready()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn change_identifiers(&self, vid: u16, pid: u16) -> Result<(), ClientError> {
+        if vid == 0 || pid == 0 {
+            return Err(ClientError::InvalidIdentifiers(vid, pid));
+        }
+        self.replug_internal(Some((vid, pid)))
+    }
+
+    /// Unplugs the target and plugs it back in, with new `(vid, pid)` identifiers if given.
+    ///
+    /// With new identifiers, the old serial number is tried first.
+    fn replug_internal(&self, identifiers: Option<(u16, u16)>) -> Result<(), ClientError> {
+        let inner_arc = self
+            .inner
+            .client_inner
+            .upgrade()
+            .ok_or(ClientError::ClientNoLongerExists)?;
+        let mut inner = inner_arc.lock().map_err(|_| ClientError::Poisoned)?;
+
+        let old_serial = self.serial_no();
+        let mut target = inner
+            .targets
+            .remove(&old_serial)
+            .ok_or(ClientError::TargetDoesNotExist(old_serial))?;
+
+        if let Err(e) = inner.bus.unplug(old_serial) {
+            inner.targets.insert(old_serial, target);
+            return Err(e.into());
+        }
+
+        let new_serial = match identifiers {
+            Some((vid, pid)) => {
+                target.vendor_id = vid;
+                target.product_id = pid;
+                // Only move to another serial if the old one was taken in the meantime.
+                match inner.plug(target, Some(old_serial)) {
+                    Err(ClientError::NoFreeSlot) => inner.plug(target, None)?,
+                    Err(ClientError::PluginFailed(_, e)) if e.is_slot_in_use() => {
+                        inner.plug(target, None)?
+                    }
+                    result => result?,
+                }
+            }
+            None => inner.plug(target, None)?,
+        };
+        self.inner.serial_no.store(new_serial, Ordering::Release);
+
+        // The snapshot thread was bound to the old device, restart it on next use.
+        *self
+            .inner
+            .notification_snapshot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+        // Registered receivers are bound to the old device as well.
+        *self.worker_tap() = Weak::new();
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "x360")]
 impl TargetHandle<Xbox360> {
+    /// Returns [`ControllerKind::Xbox360`].
+    #[inline]
+    pub fn controller_kind(&self) -> ControllerKind {
+        ControllerKind::Xbox360
+    }
+
     /// Gets the user index of a virtual Xbox 360 controller.
     ///
     /// It doesn't seem like this method is reliable for getting the dynamic player index assigned by a game.
@@ -148,7 +722,7 @@ impl TargetHandle<Xbox360> {
     /// **To reliably get the player index, use `TargetHandle<X360>::register_notification` and
     /// check the `led_number` field of the received `X360Notification`.**
     pub fn get_user_index(&self) -> Result<u32, ClientError> {
-        let index = self.inner.bus.get_x360_user_index(self.inner.serial_no)?;
+        let index = self.inner.bus.get_x360_user_index(self.serial_no())?;
         Ok(index)
     }
 
@@ -157,6 +731,11 @@ impl TargetHandle<Xbox360> {
     /// It is recommended to call this after plugging in a new controller if
     /// you want to immediately send a report to the controller.
     ///
+    /// This watches the notification thread that the target shares with
+    /// `register_notification` and its variants, starting it only if none is running.
+    /// Other receivers still get every notification, including those observed while
+    /// waiting.
+    ///
     /// # Example
     /// ```no_run
     /// use vigem_rust::{Client, X360Report};
@@ -170,11 +749,105 @@ impl TargetHandle<Xbox360> {
     /// x360.update(&X360Report::default()).unwrap();
     /// ```
     pub fn wait_for_ready(&self) -> Result<(), ClientError> {
+        self.wait_for_ready_detailed()?;
+        Ok(())
+    }
+
+    /// Like [`wait_for_ready`](Self::wait_for_ready), but reports which path the
+    /// readiness heuristic took.
+    ///
+    /// A [`ReadyOutcome::NoInitialNotification`] result means the host never sent a
+    /// notification, so a longer wait might help on systems with slow enumeration.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::{Client, target::ReadyOutcome};
+    /// let client = Client::connect().unwrap();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    ///
+    /// if x360.wait_for_ready_detailed().unwrap() == ReadyOutcome::NoInitialNotification {
+    ///     eprintln!("No notification from the host, the controller may not be ready yet");
+    /// }
+    /// ```
+    pub fn wait_for_ready_detailed(&self) -> Result<ReadyOutcome, ClientError> {
+        self.wait_for_ready_with()
+    }
+
+    /// Like [`wait_for_ready`](Self::wait_for_ready), but returns how long the readiness
+    /// handshake took.
+    ///
+    /// Enumeration times vary a lot between machines, so this is useful for picking
+    /// timeouts based on measurements rather than guesses.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// let x360 = client.new_x360_target().plugin().unwrap();
+    ///
+    /// let elapsed = x360.wait_for_ready_timed().unwrap();
+    /// println!("Controller was ready after {elapsed:?}");
+    /// ```
+    pub fn wait_for_ready_timed(&self) -> Result<Duration, ClientError> {
+        let start = Instant::now();
+        self.wait_for_ready()?;
+        Ok(start.elapsed())
+    }
+
+    /// Blocks until a notification satisfying `pred` arrives, and returns it.
+    ///
+    /// This is a more targeted alternative to [`wait_for_ready`](Self::wait_for_ready),
+    /// e.g. for games that assign the player index some time after the controller
+    /// appears. Only notifications sent after the call are considered.
+    ///
+    /// # Errors
+    /// Returns [`ClientError::NotificationTimeout`] if no matching notification arrives
+    /// within `timeout`, or the bus error reported by the notification thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    ///
+    /// let notification = x360.wait_for(
+    ///     |n| n.player_index().is_some(),
+    ///     Duration::from_secs(5),
+    /// )?;
+    /// println!("Assigned player {:?}", notification.player_index());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for<F>(&self, pred: F, timeout: Duration) -> Result<X360Notification, ClientError>
+    where
+        F: Fn(&X360Notification) -> bool,
+    {
         let (sender, receiver) = mpsc::channel();
-        self.inner
-            .bus
-            .start_x360_notification_thread(self.inner.serial_no, sender)?;
-        wait_for_notifications_internal(receiver, self.inner.serial_no)
+        self.subscribe(sender)?;
+
+        // A timeout too large to add to the current time means there is no deadline.
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            let received = match deadline {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(Ok(notification)) if pred(&notification) => return Ok(notification),
+                Ok(Ok(_)) => continue,
+                Ok(Err(bus_error)) => return Err(bus_error.into()),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(ClientError::NotificationTimeout(timeout));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ClientError::TargetDoesNotExist(self.serial_no()));
+                }
+            }
+        }
     }
 
     /// Registers to receive notifications for this Xbox 360 target.
@@ -183,8 +856,12 @@ impl TargetHandle<Xbox360> {
     /// which contain information like rumble data and the controller's player LED index.
     ///
     /// # Important
-    /// Calling this function spawns a dedicated background thread that lives as long as
-    /// the `Receiver` does.
+    /// The first call spawns a background thread that runs as long as any receiver of
+    /// this target's notifications does. The driver completes each pending request with a
+    /// single notification, so further calls, the other `register_notification_*`
+    /// variants, `last_notification` and the readiness waits all share that thread, and
+    /// each of them sees every notification. A bus error is only delivered to the
+    /// receiver registered first; the others are disconnected.
     ///
     /// # Example
     /// ```no_run
@@ -201,25 +878,247 @@ impl TargetHandle<Xbox360> {
     pub fn register_notification(
         &self,
     ) -> Result<Receiver<Result<X360Notification, BusError>>, ClientError> {
+        self.register_shared()
+    }
+
+    /// Subscribes to the raw response buffers of Xbox 360 notification requests.
+    ///
+    /// Each [`X360NotificationBuffer`] holds the driver's response byte for byte, instead
+    /// of only the fields decoded into an [`X360Notification`]. Current drivers only fill
+    /// in rumble and LED, but this lets you decode fields a future driver might add.
+    ///
+    /// # Important
+    /// Calling this function spawns a dedicated background thread that lives as long as
+    /// the `Receiver` does. It issues its own notification requests, so while the shared
+    /// thread of [`register_notification`](Self::register_notification) runs as well, the
+    /// two split the notifications between them.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// let receiver = x360.register_notification_raw_buffer().unwrap();
+    ///
+    /// while let Ok(Ok(raw)) = receiver.recv() {
+    ///     println!("Response bytes: {:02x?}", raw.buf);
+    /// }
+    /// ```
+    pub fn register_notification_raw_buffer(
+        &self,
+    ) -> Result<Receiver<Result<X360NotificationBuffer, BusError>>, ClientError> {
         let (sender, receiver) = mpsc::channel();
         self.inner
             .bus
-            .start_x360_notification_thread(self.inner.serial_no, sender)?;
+            .start_x360_raw_notification_thread(self.serial_no(), sender)?;
         Ok(receiver)
     }
 
-    /// Submits an input state report for this Xbox 360 target.
+    /// Spawns a thread that calls `f` for every notification of this target.
     ///
-    /// This is the primary method for sending controller inputs to the system.
-    /// The provided [`X360Report`] contains the state of all buttons, triggers,
-    /// and thumbsticks.
+    /// This registers a receiver like [`register_notification`](Self::register_notification)
+    /// and runs the usual receive loop on a new thread. The thread ends once the target is
+    /// unplugged or the bus reports an error; join the returned handle to wait for that.
     ///
-    /// # Warning
-    /// Calling this method immediately after plugging in the target will likely fail,
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    ///
+    /// let listener = x360.spawn_notification_listener(|notification| {
+    ///     println!("Rumble: {:?}", notification.rumble());
+    /// })?;
+    ///
+    /// drop(x360);
+    /// listener.join().unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_notification_listener<F>(&self, f: F) -> Result<JoinHandle<()>, ClientError>
+    where
+        F: FnMut(X360Notification) + Send + 'static,
+    {
+        let receiver = self.register_notification()?;
+        self.spawn_listener("x360", receiver, f)
+    }
+
+    /// Spawns a thread that calls `f` with the player index whenever it changes.
+    ///
+    /// The index is taken from the LED notifications with
+    /// [`X360Notification::player_index`], which is more reliable than `get_user_index`.
+    /// `f` is called for the first index and then for every change. LED numbers outside
+    /// 0-3 don't identify a player and are ignored.
+    ///
+    /// Like [`spawn_notification_listener`](Self::spawn_notification_listener), the thread
+    /// ends once the target is unplugged or the bus reports an error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    ///
+    /// x360.watch_player_index(|index| println!("Now player {}", index + 1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_player_index<F>(&self, mut f: F) -> Result<JoinHandle<()>, ClientError>
+    where
+        F: FnMut(u8) + Send + 'static,
+    {
+        let mut last_index = None;
+        self.spawn_notification_listener(move |notification| {
+            if let Some(index) = notification.player_index()
+                && last_index != Some(index)
+            {
+                last_index = Some(index);
+                f(index);
+            }
+        })
+    }
+
+    /// Returns the most recently received notification for this target, if any.
+    ///
+    /// This is a pull-based alternative to [`register_notification`](Self::register_notification),
+    /// useful for reading the current rumble or player LED state without running a consumer loop.
+    /// The first call attaches a snapshot to the shared notification thread of this target,
+    /// starting it if needed, which keeps the snapshot up to date for as long as this target
+    /// is alive. It returns `None` until the host sends a notification.
+    /// If the thread stops on a bus error, the next call starts a new one.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// if let Some(notification) = x360.last_notification().unwrap() {
+    ///     println!("Current state: {:?}", notification);
+    /// }
+    /// ```
+    pub fn last_notification(&self) -> Result<Option<X360Notification>, ClientError> {
+        self.last_notification_with()
+    }
+
+    /// Registers to receive notifications for this target with a bounded buffer.
+    ///
+    /// Unlike [`register_notification`](Self::register_notification), at most `capacity`
+    /// notifications are kept. When the buffer is full, the oldest notification is dropped
+    /// in favor of the newest one. This prevents unbounded memory growth for consumers
+    /// that only care about the latest feedback. A `capacity` of `0` is treated as `1`.
+    ///
+    /// # Important
+    /// Like [`register_notification`](Self::register_notification), this is fed by the
+    /// shared notification thread of the target, which runs as long as any of its
+    /// receivers does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// // Only keep the most recent notification.
+    /// let receiver = x360.register_notification_bounded(1).unwrap();
+    ///
+    /// if let Ok(Ok(notification)) = receiver.try_recv() {
+    ///     println!("Latest notification: {:?}", notification);
+    /// }
+    /// ```
+    pub fn register_notification_bounded(
+        &self,
+        capacity: usize,
+    ) -> Result<BoundedReceiver<X360Notification>, ClientError> {
+        let (sender, receiver) = drop_oldest_channel(capacity);
+        self.subscribe(sender)?;
+        Ok(BoundedReceiver { receiver })
+    }
+
+    /// Registers to receive notifications for this target as an iterator.
+    ///
+    /// Each call to `next` waits up to `timeout` for a notification. Bus errors are
+    /// yielded as `Err` items rather than silently ending the iteration. The iterator
+    /// returns `None` once no notification arrived within `timeout`, or once the
+    /// background thread has stopped.
+    ///
+    /// # Important
+    /// Like [`register_notification`](Self::register_notification), this is fed by the
+    /// shared notification thread of the target, which runs as long as any of its
+    /// receivers does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::time::Duration;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// for notification in x360.notifications_iter(Duration::from_secs(5)).unwrap() {
+    ///     match notification {
+    ///         Ok(notification) => println!("Received notification: {:?}", notification),
+    ///         Err(e) => eprintln!("Bus error: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn notifications_iter(
+        &self,
+        timeout: Duration,
+    ) -> Result<NotificationIter<X360Notification>, ClientError> {
+        let receiver = self.register_notification()?;
+        Ok(NotificationIter::new(receiver, timeout))
+    }
+
+    /// Registers to receive notifications for this target as an asynchronous stream.
+    ///
+    /// This is the `async` counterpart of [`register_notification`](Self::register_notification)
+    /// and yields the same [`X360Notification`]s.
+    ///
+    /// # Important
+    /// The stream is fed by the shared notification thread of the target, like
+    /// [`register_notification`](Self::register_notification). That thread waits on the
+    /// bus and only notices a dropped stream once the next notification arrives.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// # let x360 = client.new_x360_target().plugin()?;
+    /// let mut notifications = x360.register_notification_stream()?;
+    ///
+    /// while let Some(Ok(notification)) = notifications.next().await {
+    ///     println!("Received notification: {:?}", notification);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn register_notification_stream(
+        &self,
+    ) -> Result<impl Stream<Item = Result<X360Notification, BusError>> + use<>, ClientError> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.subscribe(sender)?;
+        Ok(UnboundedReceiverStream::new(receiver))
+    }
+
+    /// Submits an input state report for this Xbox 360 target.
+    ///
+    /// This is the primary method for sending controller inputs to the system.
+    /// The provided [`X360Report`] contains the state of all buttons, triggers,
+    /// and thumbsticks.
+    ///
+    /// # Warning
+    /// Calling this method immediately after plugging in the target will likely fail,
     /// as the system needs time to enumerate the device.
     ///
     /// To reliably send updates right after creation, you must first call [`wait_for_ready()`].
     ///
+    /// # Errors
+    /// Returns [`ClientError::ClientNoLongerExists`] if the [`Client`] has been dropped, or
+    /// [`ClientError::TargetDoesNotExist`] if the target has been unplugged.
+    ///
     /// # Example
     /// ```no_run
     /// # use vigem_rust::{Client, X360Report, X360Button};
@@ -237,18 +1136,244 @@ impl TargetHandle<Xbox360> {
     /// # }
     /// ```
     pub fn update(&self, report: &X360Report) -> Result<(), ClientError> {
-        self.inner.bus.update_x360(self.inner.serial_no, report)?;
+        let mut last_report = self.last_report()?;
+        self.submit(|bus, serial_no| bus.update_x360(serial_no, report))?;
+        *last_report = *report;
         Ok(())
     }
+
+    /// Runs a fixed-rate update loop, calling `f` to change the report and submitting it
+    /// `hz` times per second.
+    ///
+    /// The report starts out as [`X360Report::default()`] and keeps the changes `f` made on
+    /// earlier ticks. The time `f` and the submission take is subtracted from the sleep
+    /// between ticks, so the rate stays steady. The loop ends when `f` returns `false`,
+    /// without submitting that tick's report. A rate of `0` is treated as `1`.
+    ///
+    /// # Errors
+    /// Stops at the first failed submission and returns its error, see
+    /// [`update`](Self::update).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// // Hold the right trigger for two seconds.
+    /// let mut frames = 0;
+    /// x360.run_at_hz(60, |report| {
+    ///     report.right_trigger = 255;
+    ///     frames += 1;
+    ///     frames <= 120
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn run_at_hz<F>(&self, hz: u32, f: F) -> Result<(), ClientError>
+    where
+        F: FnMut(&mut X360Report) -> bool,
+    {
+        self.run_at_hz_with(hz, f, |report| self.update(report))
+    }
+
+    /// Checks whether the driver still has the virtual controller, by querying its user
+    /// index.
+    ///
+    /// Unlike [`is_attached`](Self::is_attached), which only looks at the client's own
+    /// bookkeeping, this also catches a device that the driver removed on its own. Returns
+    /// `Ok(false)` if the target was unplugged or the driver rejected the query. No report
+    /// is submitted, so the state of the controller is left as it is.
+    ///
+    /// # Errors
+    /// Returns [`ClientError::ClientNoLongerExists`] if the [`Client`] has been dropped, or
+    /// the error of a rejected query if it is transient, as the device may still be live.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let x360 = client.new_x360_target().plugin().unwrap();
+    /// if !x360.ping().unwrap() {
+    ///     eprintln!("The controller is gone, plugging in a new one");
+    /// }
+    /// ```
+    pub fn ping(&self) -> Result<bool, ClientError> {
+        self.ping_with(|bus, serial_no| bus.get_x360_user_index(serial_no).map(|_| ()))
+    }
+
+    /// Sends a raw 12-byte XUSB report to the virtual controller.
+    ///
+    /// This bypasses the typed fields of [`X360Report`] for experimenting with the
+    /// wire format, similar to how `Ds4ReportEx` exposes the raw DS4 buffer. The
+    /// bytes are decoded with [`X360Report::from_bytes`] and submitted like
+    /// [`update`](Self::update), so the result is also tracked as the last report.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::connect()?;
+    /// # let x360 = client.new_x360_target().plugin()?;
+    /// # x360.wait_for_ready()?;
+    /// // Set an otherwise unused button bit (0x0800).
+    /// x360.update_raw(&[0x00, 0x08, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_raw(&self, bytes: &[u8; 12]) -> Result<(), ClientError> {
+        self.update(&X360Report::from_bytes(bytes))
+    }
+
+    /// Submits a report like [`update`](Self::update), but drops it if the previous
+    /// report was submitted less than `min_interval` ago.
+    ///
+    /// This is a soft rate limiter against flooding the target from a tight loop. A
+    /// dropped report still returns `Ok(())`, so the next call after `min_interval` has
+    /// passed should carry the latest state. All successful submissions through this
+    /// handle count towards the interval, not only throttled ones.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Report};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// # let x360 = client.new_x360_target().plugin()?;
+    /// # x360.wait_for_ready()?;
+    /// let report = X360Report::default();
+    /// loop {
+    ///     // At most one report per millisecond reaches the bus.
+    ///     x360.update_throttled(&report, Duration::from_millis(1))?;
+    /// }
+    /// # }
+    /// ```
+    pub fn update_throttled(
+        &self,
+        report: &X360Report,
+        min_interval: Duration,
+    ) -> Result<(), ClientError> {
+        // Taken before the submission time, in the same order as `update`.
+        let mut last_report = self.last_report()?;
+        self.throttled(min_interval, |last_submit| {
+            self.submit_holding(Some(last_submit), |bus, serial_no| {
+                bus.update_x360(serial_no, report)
+            })?;
+            *last_report = *report;
+            Ok(())
+        })
+    }
+
+    /// Modifies the last submitted report in place and submits the result.
+    ///
+    /// The handle keeps track of the last report sent through [`update`](Self::update)
+    /// or `update_with`, starting from [`X360Report::default()`]. This lets callers
+    /// express incremental changes without keeping the report state themselves.
+    ///
+    /// `f` is called without holding any lock, so it may use this handle as well. An
+    /// update from another thread that lands while `f` runs is overwritten.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Button};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::connect()?;
+    /// # let x360 = client.new_x360_target().plugin()?;
+    /// # x360.wait_for_ready()?;
+    /// // Press A, then additionally pull the right trigger.
+    /// x360.update_with(|r| r.buttons.insert(X360Button::A))?;
+    /// x360.update_with(|r| r.right_trigger = 255)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_with<F: FnOnce(&mut X360Report)>(&self, f: F) -> Result<(), ClientError> {
+        // `f` runs without the report lock, so it may use this handle itself.
+        let mut report = *self.last_report()?;
+        f(&mut report);
+        self.update(&report)
+    }
+
+    /// Plays back a timed sequence of reports, e.g. a recorded input session.
+    ///
+    /// Each item pairs a report with the delay to wait before submitting it, relative
+    /// to the previous report. Reports are submitted through [`update`](Self::update),
+    /// and playback stops at the first error. This blocks the calling thread until the
+    /// sequence ends.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Button, X360Report};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// # let x360 = client.new_x360_target().plugin()?;
+    /// # x360.wait_for_ready()?;
+    /// let press = X360Report::builder().buttons(X360Button::A).build();
+    /// let release = X360Report::default();
+    ///
+    /// // Tap A twice.
+    /// x360.replay(
+    ///     [press, release, press, release]
+    ///         .into_iter()
+    ///         .map(|report| (Duration::from_millis(100), report)),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn replay<R>(&self, iter: R) -> Result<(), ClientError>
+    where
+        R: Iterator<Item = (Duration, X360Report)>,
+    {
+        for (delay, report) in iter {
+            thread::sleep(delay);
+            self.update(&report)?;
+        }
+        Ok(())
+    }
+
+    /// Moves this handle onto a dedicated thread that submits reports in the background.
+    ///
+    /// The returned [`ReportSender`] only pushes reports onto a channel, so code on a hot
+    /// path (e.g. an audio or render thread) never waits for the blocking bus call. The
+    /// thread submits the reports in order through [`update`](Self::update) and keeps the
+    /// target plugged in until every clone of the sender has been dropped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, X360Report};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let x360 = client.new_x360_target().plugin()?;
+    /// x360.wait_for_ready()?;
+    ///
+    /// let sender = x360.into_sender()?;
+    /// sender.send(X360Report::default())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_sender(self) -> Result<ReportSender<Xbox360>, ClientError> {
+        self.into_sender_with("x360", Self::update)
+    }
 }
 
 #[cfg(feature = "ds4")]
 impl TargetHandle<DualShock4> {
+    /// Returns [`ControllerKind::DualShock4`].
+    #[inline]
+    pub fn controller_kind(&self) -> ControllerKind {
+        ControllerKind::DualShock4
+    }
+
     /// Blocks until the virtual controller is fully enumerated and ready to receive updates.
     ///
     /// It is recommended to call this after plugging in a new controller if
     /// you want to immediately send a report to the controller.
     ///
+    /// This watches the notification thread that the target shares with
+    /// `register_notification` and its variants, starting it only if none is running.
+    /// Other receivers still get every notification, including those observed while
+    /// waiting.
+    ///
     /// # Example
     /// ```no_run
     /// use vigem_rust::{Client, Ds4Report};
@@ -262,11 +1387,49 @@ impl TargetHandle<DualShock4> {
     /// ds4.update(&Ds4Report::default()).unwrap();
     /// ```
     pub fn wait_for_ready(&self) -> Result<(), ClientError> {
-        let (sender, receiver) = mpsc::channel();
-        self.inner
-            .bus
-            .start_ds4_notification_thread(self.inner.serial_no, sender)?;
-        wait_for_notifications_internal(receiver, self.inner.serial_no)
+        self.wait_for_ready_detailed()?;
+        Ok(())
+    }
+
+    /// Like [`wait_for_ready`](Self::wait_for_ready), but reports which path the
+    /// readiness heuristic took.
+    ///
+    /// A [`ReadyOutcome::NoInitialNotification`] result means the host never sent a
+    /// notification, so a longer wait might help on systems with slow enumeration.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::{Client, target::ReadyOutcome};
+    /// let client = Client::connect().unwrap();
+    /// let ds4 = client.new_ds4_target().plugin().unwrap();
+    ///
+    /// if ds4.wait_for_ready_detailed().unwrap() == ReadyOutcome::NoInitialNotification {
+    ///     eprintln!("No notification from the host, the controller may not be ready yet");
+    /// }
+    /// ```
+    pub fn wait_for_ready_detailed(&self) -> Result<ReadyOutcome, ClientError> {
+        self.wait_for_ready_with()
+    }
+
+    /// Like [`wait_for_ready`](Self::wait_for_ready), but returns how long the readiness
+    /// handshake took.
+    ///
+    /// Enumeration times vary a lot between machines, so this is useful for picking
+    /// timeouts based on measurements rather than guesses.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use vigem_rust::Client;
+    /// let client = Client::connect().unwrap();
+    /// let ds4 = client.new_ds4_target().plugin().unwrap();
+    ///
+    /// let elapsed = ds4.wait_for_ready_timed().unwrap();
+    /// println!("Controller was ready after {elapsed:?}");
+    /// ```
+    pub fn wait_for_ready_timed(&self) -> Result<Duration, ClientError> {
+        let start = Instant::now();
+        self.wait_for_ready()?;
+        Ok(start.elapsed())
     }
 
     /// Registers to receive notifications for this DualShock 4 target.
@@ -275,8 +1438,12 @@ impl TargetHandle<DualShock4> {
     /// which contain information like rumble data and lightbar color commands.
     ///
     /// # Important
-    /// Calling this function spawns a dedicated background thread that lives as long as
-    /// the `Receiver` does.
+    /// The first call spawns a background thread that runs as long as any receiver of
+    /// this target's notifications does. The driver completes each pending request with a
+    /// single notification, so further calls, the other `register_notification_*`
+    /// variants, `last_notification` and the readiness waits all share that thread, and
+    /// each of them sees every notification. A bus error is only delivered to the
+    /// receiver registered first; the others are disconnected.
     ///
     /// # Example
     /// ```no_run
@@ -293,69 +1460,424 @@ impl TargetHandle<DualShock4> {
     pub fn register_notification(
         &self,
     ) -> Result<Receiver<Result<Ds4Notification, BusError>>, ClientError> {
+        self.register_shared()
+    }
+
+    /// Spawns a thread that calls `f` for every notification of this target.
+    ///
+    /// This registers a receiver like [`register_notification`](Self::register_notification)
+    /// and runs the usual receive loop on a new thread. The thread ends once the target is
+    /// unplugged or the bus reports an error; join the returned handle to wait for that.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let ds4 = client.new_ds4_target().plugin()?;
+    ///
+    /// let listener = ds4.spawn_notification_listener(|notification| {
+    ///     println!("Rumble: {:?}", notification.rumble());
+    /// })?;
+    ///
+    /// drop(ds4);
+    /// listener.join().unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_notification_listener<F>(&self, f: F) -> Result<JoinHandle<()>, ClientError>
+    where
+        F: FnMut(Ds4Notification) + Send + 'static,
+    {
+        let receiver = self.register_notification()?;
+        self.spawn_listener("ds4", receiver, f)
+    }
+
+    /// Returns the most recently received notification for this target, if any.
+    ///
+    /// This is a pull-based alternative to [`register_notification`](Self::register_notification),
+    /// useful for reading the current rumble or lightbar state without running a consumer loop.
+    /// The first call attaches a snapshot to the shared notification thread of this target,
+    /// starting it if needed, which keeps the snapshot up to date for as long as this target
+    /// is alive. It returns `None` until the host sends a notification.
+    /// If the thread stops on a bus error, the next call starts a new one.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// if let Some(notification) = ds4.last_notification().unwrap() {
+    ///     println!("Current state: {:?}", notification);
+    /// }
+    /// ```
+    pub fn last_notification(&self) -> Result<Option<Ds4Notification>, ClientError> {
+        self.last_notification_with()
+    }
+
+    /// Registers to receive notifications for this target with a bounded buffer.
+    ///
+    /// Unlike [`register_notification`](Self::register_notification), at most `capacity`
+    /// notifications are kept. When the buffer is full, the oldest notification is dropped
+    /// in favor of the newest one. This prevents unbounded memory growth for consumers
+    /// that only care about the latest feedback. A `capacity` of `0` is treated as `1`.
+    ///
+    /// # Important
+    /// Like [`register_notification`](Self::register_notification), this is fed by the
+    /// shared notification thread of the target, which runs as long as any of its
+    /// receivers does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// // Only keep the most recent notification.
+    /// let receiver = ds4.register_notification_bounded(1).unwrap();
+    ///
+    /// if let Ok(Ok(notification)) = receiver.try_recv() {
+    ///     println!("Latest notification: {:?}", notification);
+    /// }
+    /// ```
+    pub fn register_notification_bounded(
+        &self,
+        capacity: usize,
+    ) -> Result<BoundedReceiver<Ds4Notification>, ClientError> {
+        let (sender, receiver) = drop_oldest_channel(capacity);
+        self.subscribe(sender)?;
+        Ok(BoundedReceiver { receiver })
+    }
+
+    /// Registers to receive notifications for this target as an iterator.
+    ///
+    /// Each call to `next` waits up to `timeout` for a notification. Bus errors are
+    /// yielded as `Err` items rather than silently ending the iteration. The iterator
+    /// returns `None` once no notification arrived within `timeout`, or once the
+    /// background thread has stopped.
+    ///
+    /// # Important
+    /// Like [`register_notification`](Self::register_notification), this is fed by the
+    /// shared notification thread of the target, which runs as long as any of its
+    /// receivers does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::time::Duration;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// for notification in ds4.notifications_iter(Duration::from_secs(5)).unwrap() {
+    ///     match notification {
+    ///         Ok(notification) => println!("Received notification: {:?}", notification),
+    ///         Err(e) => eprintln!("Bus error: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn notifications_iter(
+        &self,
+        timeout: Duration,
+    ) -> Result<NotificationIter<Ds4Notification>, ClientError> {
+        let receiver = self.register_notification()?;
+        Ok(NotificationIter::new(receiver, timeout))
+    }
+
+    /// Registers to receive notifications for this target as an asynchronous stream.
+    ///
+    /// This is the `async` counterpart of [`register_notification`](Self::register_notification)
+    /// and yields the same [`Ds4Notification`]s.
+    ///
+    /// # Important
+    /// The stream is fed by the shared notification thread of the target, like
+    /// [`register_notification`](Self::register_notification). That thread waits on the
+    /// bus and only notices a dropped stream once the next notification arrives.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// # let ds4 = client.new_ds4_target().plugin()?;
+    /// let mut notifications = ds4.register_notification_stream()?;
+    ///
+    /// while let Some(Ok(notification)) = notifications.next().await {
+    ///     println!("Received notification: {:?}", notification);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn register_notification_stream(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Ds4Notification, BusError>> + use<>, ClientError> {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        self.subscribe(sender)?;
+        Ok(UnboundedReceiverStream::new(receiver))
+    }
+
+    /// Registers to receive notifications for this DualShock 4 target, skipping repeats.
+    ///
+    /// This works like [`register_notification`](Self::register_notification), but a
+    /// [`Ds4Notification`] is only forwarded when it differs from the previously forwarded
+    /// one. This is useful for UIs that only react to rumble or lightbar changes.
+    ///
+    /// # Important
+    /// Like [`register_notification`](Self::register_notification), this is fed by the
+    /// shared notification thread of the target. Repeats are only skipped for this
+    /// receiver.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, Ds4Notification};
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// let receiver = ds4.register_notification_changed_only().unwrap();
+    ///
+    /// // Only fires when the rumble or lightbar state actually changed.
+    /// while let Ok(Ok(notification)) = receiver.recv() {
+    ///     println!("Lightbar color changed to: {:?}", notification.lightbar);
+    /// }
+    /// ```
+    pub fn register_notification_changed_only(
+        &self,
+    ) -> Result<Receiver<Result<Ds4Notification, BusError>>, ClientError> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribe(ChangedOnlySink::new(sender))?;
+        Ok(receiver)
+    }
+
+    /// Subscribes to raw 64-byte output buffers for a DualShock 4 target.
+    ///
+    /// # Warning
+    /// Unlike the `register_notification` method, this one gets the raw
+    /// output buffer of all connected Dualshock 4 virtual controllers
+    /// via a single thread (centralized system.)
+    ///
+    /// # Important
+    /// Calling this function spawns a dedicated background thread that lives as long as
+    /// the `Receiver` does. It issues its own output requests, so several output receivers
+    /// of the same target split the output reports between them.
+    ///
+    /// This is an advanced function for applications that need to parse the raw output
+    /// report from the bus, which may contain more detailed information than the standard
+    /// [`Ds4Notification`].
+    pub fn register_notification_raw_buffer(
+        &self,
+    ) -> Result<Receiver<Result<Ds4OutputBuffer, BusError>>, ClientError> {
+        let (sender, receiver) = mpsc::channel();
+        self.inner
+            .bus
+            .start_ds4_output_thread(self.serial_no(), sender)?;
+        Ok(receiver)
+    }
+
+    /// Subscribes to decoded output reports for a DualShock 4 target.
+    ///
+    /// This listens to the same output reports as
+    /// [`register_notification_raw_buffer`](Self::register_notification_raw_buffer), but
+    /// decodes each one into a [`Ds4FullOutput`] with rumble, lightbar color, flash
+    /// timings and the report ID. The same warning about the shared output queue applies.
+    ///
+    /// # Important
+    /// Calling this function spawns a dedicated background thread that lives as long as
+    /// the `Receiver` does.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// let receiver = ds4.register_notification_parsed().unwrap();
+    ///
+    /// while let Ok(Ok(output)) = receiver.recv() {
+    ///     println!("Flash on/off: {}/{}", output.flash_on, output.flash_off);
+    /// }
+    /// ```
+    pub fn register_notification_parsed(
+        &self,
+    ) -> Result<Receiver<Result<Ds4FullOutput, BusError>>, ClientError> {
         let (sender, receiver) = mpsc::channel();
         self.inner
             .bus
-            .start_ds4_notification_thread(self.inner.serial_no, sender)?;
+            .start_ds4_parsed_output_thread(self.serial_no(), sender)?;
         Ok(receiver)
     }
 
-    /// Subscribes to raw 64-byte output buffers for a DualShock 4 target.
-    ///
-    /// # Warning
-    /// Unlike the `register_notification` method, this one gets the raw
-    /// output buffer of all connected Dualshock 4 virtual controllers
-    /// via a single thread (centralized system.)
+    /// Submits a standard input state report for this DualShock 4 target.
+    ///
+    /// This method sends a [`Ds4Report`], which covers the state of all buttons,
+    /// D-Pad, triggers, and thumbsticks. For advanced features like touchpad or
+    /// motion sensor data, use [`update_ex`](Self::update_ex) instead.
+    ///
+    /// # Warning
+    /// Calling this method immediately after plugging in the target will likely fail,
+    /// as the system needs time to enumerate the device.
+    ///
+    /// To reliably send updates right after creation, you must first call [`wait_for_ready()`].
+    ///
+    /// # Errors
+    /// Returns [`ClientError::ClientNoLongerExists`] if the [`Client`] has been dropped, or
+    /// [`ClientError::TargetDoesNotExist`] if the target has been unplugged.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, Ds4Report, Ds4Button, Ds4Dpad};
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// # let client = Client::connect()?;
+    /// # let ds4 = client.new_ds4_target().plugin()?;
+    /// # ds4.wait_for_ready()?;
+    /// let mut report = Ds4Report::default();
+    /// report.buttons = Ds4Button::CROSS.bits();
+    /// report.set_dpad(Ds4Dpad::South);
+    /// report.trigger_r = 255;
+    ///
+    /// ds4.update(&report)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update(&self, report: &Ds4Report) -> Result<(), ClientError> {
+        let mut last_report = self.last_report()?;
+        self.submit(|bus, serial_no| bus.update_ds4(serial_no, report))?;
+        *last_report = *report;
+        Ok(())
+    }
+
+    /// Runs a fixed-rate update loop, calling `f` to change the report and submitting it
+    /// `hz` times per second.
+    ///
+    /// The report starts out as [`Ds4Report::default()`] and keeps the changes `f` made on
+    /// earlier ticks. The time `f` and the submission take is subtracted from the sleep
+    /// between ticks, so the rate stays steady. The loop ends when `f` returns `false`,
+    /// without submitting that tick's report. A rate of `0` is treated as `1`.
+    ///
+    /// # Errors
+    /// Stops at the first failed submission and returns its error, see
+    /// [`update`](Self::update).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// // Hold the right trigger for two seconds.
+    /// let mut frames = 0;
+    /// ds4.run_at_hz(60, |report| {
+    ///     report.trigger_r = 255;
+    ///     frames += 1;
+    ///     frames <= 120
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn run_at_hz<F>(&self, hz: u32, f: F) -> Result<(), ClientError>
+    where
+        F: FnMut(&mut Ds4Report) -> bool,
+    {
+        self.run_at_hz_with(hz, f, |report| self.update(report))
+    }
+
+    /// Checks whether the driver still has the virtual controller, by looking for its
+    /// device among the children of the bus.
+    ///
+    /// Unlike [`is_attached`](Self::is_attached), which only looks at the client's own
+    /// bookkeeping, this also catches a device that the driver removed on its own. Returns
+    /// `Ok(false)` if the target was unplugged or is no longer listed. No report is
+    /// submitted, so the state of the controller is left as it is.
+    ///
+    /// # Errors
+    /// Returns [`ClientError::ClientNoLongerExists`] if the [`Client`] has been dropped, or
+    /// the error of a failed lookup if it is transient.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # let client = Client::connect().unwrap();
+    /// # let ds4 = client.new_ds4_target().plugin().unwrap();
+    /// if !ds4.ping().unwrap() {
+    ///     eprintln!("The controller is gone, plugging in a new one");
+    /// }
+    /// ```
+    pub fn ping(&self) -> Result<bool, ClientError> {
+        // The driver has no DS4 request that leaves the device untouched, so the device
+        // node is looked up instead.
+        self.ping_with(|bus, serial_no| {
+            bus.enumerate_children()?
+                .iter()
+                .any(|child| {
+                    child.serial_no == serial_no && child.kind == Some(TargetType::DualShock4)
+                })
+                .then_some(())
+                .ok_or(BusError::TargetNotFound(serial_no))
+        })
+    }
+
+    /// Submits a report like [`update`](Self::update), but drops it if the previous
+    /// report was submitted less than `min_interval` ago.
     ///
-    /// # Important
-    /// Calling this function spawns a dedicated background thread that lives as long as
-    /// the `Receiver` does.
+    /// This is a soft rate limiter against flooding the target from a tight loop. A
+    /// dropped report still returns `Ok(())`, so the next call after `min_interval` has
+    /// passed should carry the latest state. All successful submissions through this
+    /// handle count towards the interval, not only throttled ones.
     ///
-    /// This is an advanced function for applications that need to parse the raw output
-    /// report from the bus, which may contain more detailed information than the standard
-    /// [`Ds4Notification`].
-    pub fn register_notification_raw_buffer(
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, Ds4Report};
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// # let ds4 = client.new_ds4_target().plugin()?;
+    /// # ds4.wait_for_ready()?;
+    /// let report = Ds4Report::default();
+    /// loop {
+    ///     // At most one report per millisecond reaches the bus.
+    ///     ds4.update_throttled(&report, Duration::from_millis(1))?;
+    /// }
+    /// # }
+    /// ```
+    pub fn update_throttled(
         &self,
-    ) -> Result<Receiver<Result<Ds4OutputBuffer, BusError>>, ClientError> {
-        let (sender, receiver) = mpsc::channel();
-        self.inner
-            .bus
-            .start_ds4_output_thread(self.inner.serial_no, sender)?;
-        Ok(receiver)
+        report: &Ds4Report,
+        min_interval: Duration,
+    ) -> Result<(), ClientError> {
+        // Taken before the submission time, in the same order as `update`.
+        let mut last_report = self.last_report()?;
+        self.throttled(min_interval, |last_submit| {
+            self.submit_holding(Some(last_submit), |bus, serial_no| {
+                bus.update_ds4(serial_no, report)
+            })?;
+            *last_report = *report;
+            Ok(())
+        })
     }
 
-    /// Submits a standard input state report for this DualShock 4 target.
-    ///
-    /// This method sends a [`Ds4Report`], which covers the state of all buttons,
-    /// D-Pad, triggers, and thumbsticks. For advanced features like touchpad or
-    /// motion sensor data, use [`update_ex`](Self::update_ex) instead.
+    /// Modifies the last submitted standard report in place and submits the result.
     ///
-    /// # Warning
-    /// Calling this method immediately after plugging in the target will likely fail,
-    /// as the system needs time to enumerate the device.
+    /// The handle keeps track of the last report sent through [`update`](Self::update)
+    /// or `update_with`, starting from [`Ds4Report::default()`]. Extended reports sent
+    /// through [`update_ex`](Self::update_ex) are not tracked.
     ///
-    /// To reliably send updates right after creation, you must first call [`wait_for_ready()`].
+    /// `f` is called without holding any lock, so it may use this handle as well. An
+    /// update from another thread that lands while `f` runs is overwritten.
     ///
     /// # Example
     /// ```no_run
-    /// # use vigem_rust::{Client, Ds4Report, Ds4Button, Ds4Dpad};
+    /// # use vigem_rust::{Client, Ds4Dpad};
     /// # use std::error::Error;
     /// # fn main() -> Result<(), Box<dyn Error>> {
     /// # let client = Client::connect()?;
     /// # let ds4 = client.new_ds4_target().plugin()?;
     /// # ds4.wait_for_ready()?;
-    /// let mut report = Ds4Report::default();
-    /// report.buttons = Ds4Button::CROSS.bits();
-    /// report.set_dpad(Ds4Dpad::South);
-    /// report.trigger_r = 255;
-    ///
-    /// ds4.update(&report)?;
+    /// ds4.update_with(|r| r.set_dpad(Ds4Dpad::North))?;
+    /// ds4.update_with(|r| r.trigger_l = 128)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn update(&self, report: &Ds4Report) -> Result<(), ClientError> {
-        self.inner.bus.update_ds4(self.inner.serial_no, report)?;
-        Ok(())
+    pub fn update_with<F: FnOnce(&mut Ds4Report)>(&self, f: F) -> Result<(), ClientError> {
+        // `f` runs without the report lock, so it may use this handle itself.
+        let mut report = *self.last_report()?;
+        f(&mut report);
+        self.update(&report)
     }
 
     /// Submits an extended input state report for this DualShock 4 target.
@@ -370,6 +1892,10 @@ impl TargetHandle<DualShock4> {
     ///
     /// To reliably send updates right after creation, you must first call [`wait_for_ready()`].
     ///
+    /// # Errors
+    /// Returns [`ClientError::ClientNoLongerExists`] if the [`Client`] has been dropped, or
+    /// [`ClientError::TargetDoesNotExist`] if the target has been unplugged.
+    ///
     /// # Example
     /// ```no_run
     /// # use vigem_rust::{Client, controller::ds4::Ds4ReportEx};
@@ -392,18 +1918,411 @@ impl TargetHandle<DualShock4> {
     /// # }
     /// ```
     pub fn update_ex(&self, report: &Ds4ReportEx) -> Result<(), ClientError> {
-        self.inner.bus.update_ds4_ex(self.inner.serial_no, report)?;
+        self.submit(|bus, serial_no| bus.update_ds4_ex(serial_no, report))?;
+        Ok(())
+    }
+
+    /// Moves this handle onto a dedicated thread that submits reports in the background.
+    ///
+    /// The returned [`ReportSender`] only pushes reports onto a channel, so code on a hot
+    /// path (e.g. an audio or render thread) never waits for the blocking bus call. The
+    /// thread submits the reports in order through [`update`](Self::update) and keeps the
+    /// target plugged in until every clone of the sender has been dropped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::{Client, Ds4Report};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let ds4 = client.new_ds4_target().plugin()?;
+    /// ds4.wait_for_ready()?;
+    ///
+    /// let sender = ds4.into_sender()?;
+    /// sender.send(Ds4Report::default())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_sender(self) -> Result<ReportSender<DualShock4>, ClientError> {
+        self.into_sender_with("ds4", Self::update)
+    }
+}
+
+/// Submits reports to a virtual controller from a dedicated background thread.
+///
+/// Obtain one via `TargetHandle::into_sender`. Cloning it is cheap, and all clones feed
+/// the same thread. The thread exits and releases its [`TargetHandle`] once every clone
+/// is dropped.
+pub struct ReportSender<T: TargetKind> {
+    sender: mpsc::Sender<T::Report>,
+    // The most recent submission failure, reported by the next `send`.
+    error: Arc<Mutex<Option<ClientError>>>,
+    serial_no: u32,
+}
+
+impl<T: TargetKind> ReportSender<T> {
+    /// Queues `report` for submission without waiting for the bus.
+    ///
+    /// Submission happens asynchronously, so a failure is only reported by the next
+    /// call. Once the target is gone, the background thread stops and every further
+    /// call fails.
+    ///
+    /// # Errors
+    /// Returns the error of an earlier failed submission, or
+    /// [`ClientError::TargetDoesNotExist`] if the background thread has stopped.
+    pub fn send(&self, report: T::Report) -> Result<(), ClientError> {
+        if let Some(e) = self
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            return Err(e);
+        }
+
+        self.sender
+            .send(report)
+            .map_err(|_| ClientError::TargetDoesNotExist(self.serial_no))
+    }
+}
+
+impl<T: TargetKind> Clone for ReportSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            error: self.error.clone(),
+            serial_no: self.serial_no,
+        }
+    }
+}
+
+/// A [`TargetHandle`] that unplugs its controller when dropped, even if other clones
+/// of the handle are still alive.
+///
+/// Obtain one via `TargetHandle::scoped`. It dereferences to the wrapped handle, so all
+/// of its methods are available directly.
+pub struct ScopedTarget<T: TargetKind> {
+    handle: TargetHandle<T>,
+}
+
+impl<T: TargetKind> Deref for ScopedTarget<T> {
+    type Target = TargetHandle<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handle
+    }
+}
+
+impl<T: TargetKind> Drop for ScopedTarget<T> {
+    fn drop(&mut self) {
+        // Nothing to report to: the target may already be gone, and a failed driver
+        // unplug reaches `Client::on_unplug_error`.
+        let _ = self.handle.unplug();
+    }
+}
+
+/// Submits extended DS4 reports with a `timestamp` derived from the elapsed time.
+///
+/// A real DS4 advances the timestamp of every report by the time passed since the
+/// previous one, in ticks of about 5.33 µs. Some games use it to tell fresh motion data
+/// from repeated reports, so leaving it at zero can make gyro input look stale. The
+/// updater overwrites the timestamp of each report with the time since its first
+/// report, wrapping around like the real counter.
+///
+/// # Example
+/// ```no_run
+/// # use vigem_rust::Client;
+/// # use vigem_rust::controller::ds4::Ds4ReportEx;
+/// # use vigem_rust::target::Ds4ExUpdater;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::connect()?;
+/// let ds4 = client.new_ds4_target().plugin()?;
+/// ds4.wait_for_ready()?;
+///
+/// let mut updater = Ds4ExUpdater::new(ds4);
+/// let mut report = Ds4ReportEx::default();
+/// report.set_gyro(0.0, 90.0, 0.0);
+/// updater.update_ex(&report)?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "ds4")]
+pub struct Ds4ExUpdater {
+    handle: TargetHandle<DualShock4>,
+    // When the first report was submitted, the zero point of the timestamp.
+    start: Option<Instant>,
+}
+
+#[cfg(feature = "ds4")]
+impl Ds4ExUpdater {
+    /// Creates an updater that submits reports through `handle`.
+    pub fn new(handle: TargetHandle<DualShock4>) -> Self {
+        Self {
+            handle,
+            start: None,
+        }
+    }
+
+    /// Returns the handle reports are submitted through.
+    #[inline]
+    pub fn handle(&self) -> &TargetHandle<DualShock4> {
+        &self.handle
+    }
+
+    /// Submits `report` like `TargetHandle::update_ex`, with its `timestamp` replaced.
+    ///
+    /// # Errors
+    /// Fails in the same cases as `TargetHandle::update_ex`.
+    pub fn update_ex(&mut self, report: &Ds4ReportEx) -> Result<(), ClientError> {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        // Computed from the total elapsed time rather than per call, so the rounding
+        // of individual intervals doesn't accumulate. The cast wraps like the real counter.
+        let ticks = (start.elapsed().as_micros() * 3 / 16) as u16;
+
+        let mut report = *report;
+        report.set_timestamp(ticks);
+        self.handle.update_ex(&report)
+    }
+}
+
+/// A notification received by a [`NotificationPump`].
+#[derive(Debug)]
+pub enum Event {
+    #[cfg(feature = "x360")]
+    X360(X360Notification),
+    #[cfg(feature = "ds4")]
+    Ds4(Ds4Notification),
+    /// The bus reported an error for the target. Its notifications stop afterwards, so
+    /// the target is removed from the pump.
+    Error(BusError),
+}
+
+enum PumpReceiver {
+    #[cfg(feature = "x360")]
+    X360(Receiver<Result<X360Notification, BusError>>),
+    #[cfg(feature = "ds4")]
+    Ds4(Receiver<Result<Ds4Notification, BusError>>),
+}
+
+impl PumpReceiver {
+    fn try_recv(&self) -> Result<Result<Event, BusError>, TryRecvError> {
+        match self {
+            #[cfg(feature = "x360")]
+            PumpReceiver::X360(receiver) => receiver.try_recv().map(|item| item.map(Event::X360)),
+            #[cfg(feature = "ds4")]
+            PumpReceiver::Ds4(receiver) => receiver.try_recv().map(|item| item.map(Event::Ds4)),
+        }
+    }
+}
+
+/// Receives the notifications of many virtual controllers on a single thread.
+///
+/// `std::sync::mpsc` can't wait on several receivers at once, so [`poll`](Self::poll)
+/// checks them round-robin and sleeps briefly between passes. Targets are removed
+/// automatically once their notifications stop, e.g. after they were unplugged.
+///
+/// # Example
+/// ```no_run
+/// # use vigem_rust::{Client, target::{Event, NotificationPump}};
+/// # use std::time::Duration;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::connect()?;
+/// let x360 = client.new_x360_target().plugin()?;
+/// let ds4 = client.new_ds4_target().plugin()?;
+///
+/// let mut pump = NotificationPump::new();
+/// pump.add_x360(&x360)?;
+/// pump.add_ds4(&ds4)?;
+///
+/// while !pump.is_empty() {
+///     match pump.poll(Duration::from_millis(100)) {
+///         Some((serial_no, Event::X360(n))) => println!("{serial_no}: {:?}", n.rumble()),
+///         Some((serial_no, Event::Ds4(n))) => println!("{serial_no}: {:?}", n.lightbar),
+///         Some((serial_no, Event::Error(e))) => eprintln!("{serial_no}: {e}"),
+///         None => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct NotificationPump {
+    sources: Vec<(u32, PumpReceiver)>,
+    // Where the next pass starts, so a busy target can't starve the others.
+    next: usize,
+}
+
+impl NotificationPump {
+    /// How long [`poll`](Self::poll) sleeps when no receiver has anything queued.
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+    /// Creates an empty pump.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a notification receiver for `handle` and adds it to the pump.
+    #[cfg(feature = "x360")]
+    pub fn add_x360(&mut self, handle: &TargetHandle<Xbox360>) -> Result<(), ClientError> {
+        let receiver = handle.register_notification()?;
+        self.sources
+            .push((handle.serial_no(), PumpReceiver::X360(receiver)));
+        Ok(())
+    }
+
+    /// Registers a notification receiver for `handle` and adds it to the pump.
+    #[cfg(feature = "ds4")]
+    pub fn add_ds4(&mut self, handle: &TargetHandle<DualShock4>) -> Result<(), ClientError> {
+        let receiver = handle.register_notification()?;
+        self.sources
+            .push((handle.serial_no(), PumpReceiver::Ds4(receiver)));
         Ok(())
     }
+
+    /// Returns the number of targets still serviced by the pump.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if no targets are left to service.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Waits up to `timeout` for a notification from any of the targets.
+    ///
+    /// Returns the serial number of the target alongside the event, or `None` if nothing
+    /// arrived in time.
+    pub fn poll(&mut self, timeout: Duration) -> Option<(u32, Event)> {
+        // A timeout too large to add to the current time means there is no deadline.
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            if let Some(event) = self.try_poll() {
+                return Some(event);
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                // Without targets nothing can ever arrive.
+                None if self.is_empty() => Duration::ZERO,
+                None => Self::POLL_INTERVAL,
+            };
+            if remaining.is_zero() {
+                return None;
+            }
+            thread::sleep(remaining.min(Self::POLL_INTERVAL));
+        }
+    }
+
+    /// Checks every receiver once, starting where the previous pass left off.
+    fn try_poll(&mut self) -> Option<(u32, Event)> {
+        // At most one source is removed per iteration, so `sources` is never empty here.
+        for _ in 0..self.sources.len() {
+            let index = self.next % self.sources.len();
+            let (serial_no, receiver) = &self.sources[index];
+            let serial_no = *serial_no;
+
+            match receiver.try_recv() {
+                Ok(Ok(event)) => {
+                    self.next = index + 1;
+                    return Some((serial_no, event));
+                }
+                Ok(Err(e)) => {
+                    self.sources.remove(index);
+                    self.next = index;
+                    return Some((serial_no, Event::Error(e)));
+                }
+                Err(TryRecvError::Empty) => self.next = index + 1,
+                Err(TryRecvError::Disconnected) => {
+                    self.sources.remove(index);
+                    self.next = index;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over the notifications of a virtual controller.
+///
+/// Obtain one via `TargetHandle::notifications_iter`. Each call to `next` blocks
+/// for at most the configured timeout.
+pub struct NotificationIter<N> {
+    receiver: Receiver<Result<N, BusError>>,
+    timeout: Duration,
+}
+
+impl<N> NotificationIter<N> {
+    #[inline]
+    pub(crate) fn new(receiver: Receiver<Result<N, BusError>>, timeout: Duration) -> Self {
+        Self { receiver, timeout }
+    }
+}
+
+impl<N> Iterator for NotificationIter<N> {
+    type Item = Result<N, BusError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Both a timeout and a disconnected notification thread end the iteration.
+        self.receiver.recv_timeout(self.timeout).ok()
+    }
+}
+
+/// A bounded receiver of notifications for a virtual controller.
+///
+/// Obtain one via `TargetHandle::register_notification_bounded`. It holds at most the
+/// configured number of notifications; when it is full, the oldest one is dropped to
+/// make room for the newest, so the background thread never blocks on a slow consumer.
+pub struct BoundedReceiver<N> {
+    receiver: Arc<SharedReceiver<N>>,
+}
+
+impl<N> BoundedReceiver<N> {
+    #[inline]
+    fn receiver(&self) -> MutexGuard<'_, Receiver<Result<N, BusError>>> {
+        self.receiver.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Blocks until a notification is available. See [`Receiver::recv`].
+    pub fn recv(&self) -> Result<Result<N, BusError>, RecvError> {
+        self.receiver().recv()
+    }
+
+    /// Returns a notification if one is available, without blocking. See [`Receiver::try_recv`].
+    pub fn try_recv(&self) -> Result<Result<N, BusError>, TryRecvError> {
+        self.receiver().try_recv()
+    }
+
+    /// Waits up to `timeout` for a notification. See [`Receiver::recv_timeout`].
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Result<N, BusError>, RecvTimeoutError> {
+        self.receiver().recv_timeout(timeout)
+    }
+}
+
+/// Describes how [`TargetHandle::wait_for_ready_detailed`] decided the target was ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadyOutcome {
+    /// Notifications arrived from the host and then stopped for the settle period.
+    StabilizedAfterActivity,
+    /// No notification arrived within the initial wait, so the target was assumed ready.
+    NoInitialNotification,
 }
 
 /// A builder for creating and plugging in a new virtual target.
 ///
 /// Obtain a [`TargetBuilder`] from [`Client::new_x360_target()`] or [`Client::new_ds4_target()`].
+///
+/// The Vendor ID and Product ID are the only parts of the device identity that the
+/// ViGEmBus driver allows to be customized. Device strings such as the USB serial
+/// number or manufacturer name are assigned by the driver and cannot be overridden.
 pub struct TargetBuilder<'a, T> {
     client: &'a Client,
     vid: Option<u16>,
     pid: Option<u16>,
+    serial: Option<u32>,
+    skip_driver_wait: bool,
     _marker: PhantomData<T>,
 }
 
@@ -414,6 +2333,8 @@ impl<'a, T> TargetBuilder<'a, T> {
             client,
             vid: None,
             pid: None,
+            serial: None,
+            skip_driver_wait: false,
             _marker: PhantomData,
         }
     }
@@ -422,6 +2343,8 @@ impl<'a, T> TargetBuilder<'a, T> {
     /// Sets a custom Vendor ID (VID) for this virtual device.
     ///
     /// If not set, the default VID for the controller type will be used.
+    /// A value of `0` is invalid and makes `plugin` fail with
+    /// [`ClientError::InvalidIdentifiers`].
     pub fn with_vid(mut self, vid: u16) -> Self {
         self.vid = Some(vid);
         self
@@ -431,14 +2354,84 @@ impl<'a, T> TargetBuilder<'a, T> {
     /// Sets a custom Product ID (PID) for this virtual device.
     ///
     /// If not set, the default PID for the controller type will be used.
+    /// A value of `0` is invalid and makes `plugin` fail with
+    /// [`ClientError::InvalidIdentifiers`].
     pub fn with_pid(mut self, pid: u16) -> Self {
         self.pid = Some(pid);
         self
     }
+
+    #[inline]
+    /// Requests a specific bus slot (serial number) for this virtual device.
+    ///
+    /// If not set, the first free slot starting at 1 will be used. If the requested
+    /// slot is already occupied, plugging in fails with [`ClientError::NoFreeSlot`].
+    ///
+    /// Serial numbers run from 1 to the client's `max_targets`. Plugging in with a serial
+    /// outside that range fails with [`ClientError::InvalidSerial`].
+    pub fn with_serial(mut self, serial: u32) -> Self {
+        self.serial = Some(serial);
+        self
+    }
+
+    #[inline]
+    /// Skips the driver's "wait device ready" request when plugging in.
+    ///
+    /// After adding the target, plugging in normally asks the driver to block until the
+    /// device is ready. That request doesn't reliably signal readiness and can take a
+    /// while, so skipping it shaves latency off every plug. Use `wait_for_ready` on the
+    /// returned handle before sending updates either way.
+    ///
+    /// The setting also applies when the target is plugged in again by `replug` or
+    /// `change_identifiers`.
+    pub fn skip_driver_wait(mut self) -> Self {
+        self.skip_driver_wait = true;
+        self
+    }
+
+    /// Tries to plug in `target` up to `attempts` times, sleeping `delay` between tries.
+    ///
+    /// Only bus failures and [`ClientError::NoFreeSlot`] are retried, since those can
+    /// clear up once the driver has settled. At least one attempt is always made.
+    fn plugin_retry_internal(
+        &self,
+        target: Target,
+        attempts: usize,
+        delay: Duration,
+    ) -> Result<TargetHandle<T>, ClientError>
+    where
+        T: TargetKind,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.client.plugin_internal(target, self.serial) {
+                Err(
+                    ClientError::BusError(_)
+                    | ClientError::PluginFailed(..)
+                    | ClientError::NoFreeSlot,
+                ) if attempt < attempts => {
+                    attempt += 1;
+                    thread::sleep(delay);
+                }
+                result => return result,
+            }
+        }
+    }
 }
 
 #[cfg(feature = "x360")]
 impl<'a> TargetBuilder<'a, Xbox360> {
+    fn target(&self) -> Target {
+        let (default_vid, default_pid) = TargetType::Xbox360.get_identifiers();
+        Target {
+            kind: TargetType::Xbox360,
+            serial_no: 0, // Will be filled in by the client
+            vendor_id: self.vid.unwrap_or(default_vid),
+            product_id: self.pid.unwrap_or(default_pid),
+            skip_driver_wait: self.skip_driver_wait,
+        }
+    }
+
     /// Plugs the configured target into the ViGEm bus.
     ///
     /// **WARNING:** The virtual controller may not be immediately ready for input updates
@@ -452,19 +2445,63 @@ impl<'a> TargetBuilder<'a, Xbox360> {
     /// On success, this consumes the builder and returns a [`TargetHandle`] which can
     /// be used to control the virtual device.
     pub fn plugin(self) -> Result<TargetHandle<Xbox360>, ClientError> {
-        let (default_vid, default_pid) = TargetType::Xbox360.get_identifiers();
-        let target = Target {
-            kind: TargetType::Xbox360,
-            serial_no: 0, // Will be filled in by the client
-            vendor_id: self.vid.unwrap_or(default_vid),
-            product_id: self.pid.unwrap_or(default_pid),
-        };
-        self.client.plugin_internal(target)
+        self.client.plugin_internal(self.target(), self.serial)
+    }
+
+    /// Plugs the configured target into the ViGEm bus and returns the assigned serial.
+    ///
+    /// This behaves exactly like [`plugin`](Self::plugin), but additionally returns the
+    /// bus slot (serial number) that was chosen for the virtual controller. This is
+    /// useful for logging which slot maps to which logical controller.
+    pub fn plugin_with_serial(self) -> Result<(TargetHandle<Xbox360>, u32), ClientError> {
+        let handle = self.plugin()?;
+        let serial_no = handle.serial_no();
+        Ok((handle, serial_no))
+    }
+
+    /// Plugs the configured target into the ViGEm bus, retrying on failure.
+    ///
+    /// Right after another target was unplugged, the driver may briefly reject new
+    /// targets while it settles. This retries bus errors and [`ClientError::NoFreeSlot`]
+    /// up to `attempts` times in total, sleeping `delay` between tries. Other errors,
+    /// as well as the error of the last attempt, are returned as-is.
+    ///
+    /// The same readiness caveats as for [`plugin`](Self::plugin) apply.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let target = client
+    ///     .new_x360_target()
+    ///     .plugin_retry(5, Duration::from_millis(200))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn plugin_retry(
+        self,
+        attempts: usize,
+        delay: Duration,
+    ) -> Result<TargetHandle<Xbox360>, ClientError> {
+        self.plugin_retry_internal(self.target(), attempts, delay)
     }
 }
 
 #[cfg(feature = "ds4")]
 impl<'a> TargetBuilder<'a, DualShock4> {
+    fn target(&self) -> Target {
+        let (default_vid, default_pid) = TargetType::DualShock4.get_identifiers();
+        Target {
+            kind: TargetType::DualShock4,
+            serial_no: 0, // Will be filled in by the client
+            vendor_id: self.vid.unwrap_or(default_vid),
+            product_id: self.pid.unwrap_or(default_pid),
+            skip_driver_wait: self.skip_driver_wait,
+        }
+    }
+
     /// Plugs the configured target into the ViGEm bus.
     ///
     /// **WARNING:** The virtual controller may not be immediately ready for input updates
@@ -477,16 +2514,52 @@ impl<'a> TargetBuilder<'a, DualShock4> {
     ///
     /// On success, this consumes the builder and returns a [`TargetHandle`] which can
     /// be used to control the virtual device.
+    ///
+    /// The controller always appears as a USB-connected DualShock 4. ViGEmBus has no
+    /// plugin option for the connection type, so a Bluetooth DualShock 4 (and its
+    /// different report format) can't be emulated.
     pub fn plugin(self) -> Result<TargetHandle<DualShock4>, ClientError> {
-        // Same here, using the concrete type.
-        let (default_vid, default_pid) = TargetType::DualShock4.get_identifiers();
-        let target = Target {
-            kind: TargetType::DualShock4,
-            serial_no: 0,
-            vendor_id: self.vid.unwrap_or(default_vid),
-            product_id: self.pid.unwrap_or(default_pid),
-        };
-        self.client.plugin_internal(target)
+        self.client.plugin_internal(self.target(), self.serial)
+    }
+
+    /// Plugs the configured target into the ViGEm bus and returns the assigned serial.
+    ///
+    /// This behaves exactly like [`plugin`](Self::plugin), but additionally returns the
+    /// bus slot (serial number) that was chosen for the virtual controller. This is
+    /// useful for logging which slot maps to which logical controller.
+    pub fn plugin_with_serial(self) -> Result<(TargetHandle<DualShock4>, u32), ClientError> {
+        let handle = self.plugin()?;
+        let serial_no = handle.serial_no();
+        Ok((handle, serial_no))
+    }
+
+    /// Plugs the configured target into the ViGEm bus, retrying on failure.
+    ///
+    /// Right after another target was unplugged, the driver may briefly reject new
+    /// targets while it settles. This retries bus errors and [`ClientError::NoFreeSlot`]
+    /// up to `attempts` times in total, sleeping `delay` between tries. Other errors,
+    /// as well as the error of the last attempt, are returned as-is.
+    ///
+    /// The same readiness caveats as for [`plugin`](Self::plugin) apply.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use vigem_rust::Client;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::connect()?;
+    /// let target = client
+    ///     .new_ds4_target()
+    ///     .plugin_retry(5, Duration::from_millis(200))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn plugin_retry(
+        self,
+        attempts: usize,
+        delay: Duration,
+    ) -> Result<TargetHandle<DualShock4>, ClientError> {
+        self.plugin_retry_internal(self.target(), attempts, delay)
     }
 }
 
@@ -507,7 +2580,7 @@ impl<'a> TargetBuilder<'a, DualShock4> {
 pub(crate) fn wait_for_notifications_internal<N>(
     receiver: Receiver<Result<N, BusError>>,
     serial_no: u32,
-) -> Result<(), ClientError> {
+) -> Result<ReadyOutcome, ClientError> {
     // We wait for the first notification. If it doesnt come within 500ms,
     // then chances are the device is ready for receiving updates.
     match receiver.recv_timeout(Duration::from_millis(500)) {
@@ -517,9 +2590,11 @@ pub(crate) fn wait_for_notifications_internal<N>(
         Ok(Err(bus_error)) => {
             return Err(bus_error.into());
         }
-        Err(RecvTimeoutError::Timeout) => return Ok(()),
-        Err(_) => {
-            return Err(ClientError::TargetDoesNotExist(serial_no));
+        Err(RecvTimeoutError::Timeout) => return Ok(ReadyOutcome::NoInitialNotification),
+        Err(RecvTimeoutError::Disconnected) => {
+            // Nothing was received yet, so the notification thread stopped on its own
+            // (e.g. it panicked or its IOCTL loop failed) rather than the target vanishing.
+            return Err(BusError::NotificationThreadStopped.into());
         }
     }
 
@@ -542,5 +2617,5 @@ pub(crate) fn wait_for_notifications_internal<N>(
         }
     }
 
-    Ok(())
+    Ok(ReadyOutcome::StabilizedAfterActivity)
 }
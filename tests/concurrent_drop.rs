@@ -0,0 +1,65 @@
+//! Dropping a client and its last target handle at the same time must unplug the target
+//! exactly once, whichever of the two drops wins the race.
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use vigem_rust::Client;
+use vigem_rust::mock::{MockBus, MockCall};
+
+// Enough rounds to hit both orders of the race on a typical machine.
+const ROUNDS: usize = 500;
+
+fn unplug_count(bus: &MockBus) -> usize {
+    bus.calls()
+        .iter()
+        .filter(|call| matches!(call, MockCall::Unplug { .. }))
+        .count()
+}
+
+/// Drops `client` on this thread and `handle` on another, releasing both at once.
+fn drop_concurrently<H: Send + 'static>(client: Client, handle: H) {
+    let barrier = Arc::new(Barrier::new(2));
+    let other = {
+        let barrier = Arc::clone(&barrier);
+        thread::spawn(move || {
+            barrier.wait();
+            drop(handle);
+        })
+    };
+
+    barrier.wait();
+    drop(client);
+    other.join().unwrap();
+}
+
+#[cfg(feature = "x360")]
+#[test]
+fn x360_target_is_unplugged_once() {
+    for _ in 0..ROUNDS {
+        let (client, bus) = Client::mock();
+        let x360 = client.new_x360_target().plugin().unwrap();
+
+        drop_concurrently(client, x360);
+
+        assert_eq!(unplug_count(&bus), 1);
+        assert!(bus.plugged().is_empty());
+    }
+}
+
+#[cfg(feature = "ds4")]
+#[test]
+fn ds4_target_with_listener_is_unplugged_once() {
+    for _ in 0..ROUNDS {
+        let (client, bus) = Client::mock();
+        let ds4 = client.new_ds4_target().plugin().unwrap();
+        let notifications = ds4.register_notification().unwrap();
+
+        drop_concurrently(client, ds4);
+
+        assert_eq!(unplug_count(&bus), 1);
+        assert!(bus.plugged().is_empty());
+        // The unplug ends the notification thread instead of leaving it waiting.
+        assert!(matches!(notifications.recv(), Ok(Err(_)) | Err(_)));
+    }
+}
@@ -0,0 +1,40 @@
+//! Draining a receiver drops the notifications queued in it, but not the ones sent later.
+
+use std::time::Duration;
+
+use vigem_rust::{Client, X360Notification};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn rumble(large_motor: u8) -> X360Notification {
+    X360Notification {
+        large_motor,
+        small_motor: 0,
+        led_number: 0,
+    }
+}
+
+#[test]
+fn drain_discards_queued_notifications() {
+    let (client, bus) = Client::mock();
+    let x360 = client.new_x360_target().plugin().unwrap();
+    let receiver = x360.register_notification().unwrap();
+    // Fed by the same thread, so once it has the last notification, `receiver` has all.
+    let witness = x360.register_notification().unwrap();
+
+    for large_motor in 1..=3 {
+        bus.send_x360_notification(1, rumble(large_motor));
+    }
+    for large_motor in 1..=3 {
+        assert_eq!(
+            witness.recv_timeout(TIMEOUT).unwrap().unwrap(),
+            rumble(large_motor)
+        );
+    }
+
+    x360.drain_notifications(&receiver).unwrap();
+    assert!(receiver.try_recv().is_err());
+
+    bus.send_x360_notification(1, rumble(4));
+    assert_eq!(receiver.recv_timeout(TIMEOUT).unwrap().unwrap(), rumble(4));
+}
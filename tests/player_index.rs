@@ -0,0 +1,35 @@
+//! The player index reported to `watch_player_index` follows the LED number sent by the
+//! bus, which ViGEmBus has already decoded into 0-3.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use vigem_rust::{Client, X360Notification};
+
+fn led(led_number: u8) -> X360Notification {
+    X360Notification {
+        large_motor: 0,
+        small_motor: 0,
+        led_number,
+    }
+}
+
+#[test]
+fn watch_player_index_reports_each_change() {
+    let (client, bus) = Client::mock();
+    let x360 = client.new_x360_target().plugin().unwrap();
+
+    let (sender, indices) = mpsc::channel();
+    x360.watch_player_index(move |index| sender.send(index).unwrap())
+        .unwrap();
+
+    // Repeats and values outside 0-3 don't reach the callback.
+    for led_number in [0, 0, 1, 4, 2, 3, 3] {
+        bus.send_x360_notification(1, led(led_number));
+    }
+
+    for expected in 0..=3 {
+        assert_eq!(indices.recv_timeout(Duration::from_secs(5)), Ok(expected));
+    }
+    assert!(indices.recv_timeout(Duration::from_millis(100)).is_err());
+}
@@ -0,0 +1,90 @@
+//! The driver completes each pending notification request with a single notification, and
+//! so does the mock. Every consumer of a target's notifications must still see all of them.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use vigem_rust::Client;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Polls `f` until it returns `true`, failing the test after [`TIMEOUT`].
+fn eventually(mut f: impl FnMut() -> bool) {
+    let deadline = Instant::now() + TIMEOUT;
+    while !f() {
+        assert!(Instant::now() < deadline, "condition not met in time");
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[cfg(feature = "x360")]
+#[test]
+fn x360_consumers_each_see_every_notification() {
+    use vigem_rust::X360Notification;
+
+    let (client, bus) = Client::mock();
+    let x360 = client.new_x360_target().plugin().unwrap();
+
+    let first = x360.register_notification().unwrap();
+    let bounded = x360.register_notification_bounded(16).unwrap();
+    assert_eq!(x360.last_notification().unwrap(), None);
+    let waiter = {
+        let x360 = x360.clone();
+        thread::spawn(move || x360.wait_for(|n| n.led_number == 3, TIMEOUT))
+    };
+    // Give the waiter time to attach before anything is sent.
+    thread::sleep(Duration::from_millis(100));
+
+    let sent: Vec<_> = (0..4)
+        .map(|led_number| X360Notification {
+            large_motor: 10 * led_number,
+            small_motor: 0,
+            led_number,
+        })
+        .collect();
+    for &notification in &sent {
+        bus.send_x360_notification(1, notification);
+    }
+
+    for &expected in &sent {
+        assert_eq!(first.recv_timeout(TIMEOUT).unwrap().unwrap(), expected);
+        assert_eq!(bounded.recv_timeout(TIMEOUT).unwrap().unwrap(), expected);
+    }
+    assert_eq!(waiter.join().unwrap().unwrap(), sent[3]);
+    eventually(|| x360.last_notification().unwrap() == Some(sent[3]));
+}
+
+#[cfg(feature = "ds4")]
+#[test]
+fn ds4_consumers_each_see_every_notification() {
+    use vigem_rust::{Ds4LightbarColor, Ds4Notification};
+
+    let (client, bus) = Client::mock();
+    let ds4 = client.new_ds4_target().plugin().unwrap();
+
+    let all = ds4.register_notification().unwrap();
+    let changed = ds4.register_notification_changed_only().unwrap();
+
+    let notification = |red| Ds4Notification {
+        large_motor: 0,
+        small_motor: 0,
+        lightbar: Ds4LightbarColor::new(red, 0, 0),
+    };
+    for red in [1, 1, 2] {
+        bus.send_ds4_notification(1, notification(red));
+    }
+
+    for red in [1, 1, 2] {
+        assert_eq!(
+            all.recv_timeout(TIMEOUT).unwrap().unwrap(),
+            notification(red)
+        );
+    }
+    for red in [1, 2] {
+        assert_eq!(
+            changed.recv_timeout(TIMEOUT).unwrap().unwrap(),
+            notification(red)
+        );
+    }
+    assert!(changed.recv_timeout(Duration::from_millis(100)).is_err());
+}